@@ -16,14 +16,32 @@ pub enum MetaError {
     FileNotFound { path: String },
     #[error("could not determine name from {file}")]
     Name { file: String },
+    #[error("pandoc is needed to build binary output for {file}")]
+    Binary { file: String },
     #[error("pandoc could not write to buffer for {file}")]
     Pandoc { file: String },
     #[error("undefined expansion: {val}\n\tin {path}")]
     UndefinedExpand { val: String, path: String },
     #[error("undefined call to default.meta: {pattern}\n\tin {path}")]
     UndefinedDefault { pattern: String, path: String },
+    #[error("expansion cycle: {chain}")]
+    Cycle { chain: String },
+    #[error("maximum expansion depth of {depth} reached at {path}")]
+    Depth { depth: usize, path: String },
     #[error("the parser cannot resolve this input: {input}")]
     UnreachableRule { input: String },
+    #[error("could not expand glob {pattern}: {error}")]
+    Glob { pattern: String, error: String },
+    #[error("GLOB() can only be used to define arrays: {input}")]
+    GlobVariable { input: String },
+    #[error("{first} and {second} both build to {path}")]
+    Collision {
+        path: String,
+        first: String,
+        second: String,
+    },
+    #[error("{feature} can't be built without absolute urls, so --base-url has to be set")]
+    BaseUrl { feature: String },
     #[error("unknown option in header: {opt}")]
     Header { opt: String },
     #[error("{file}\n{error}")]
@@ -61,11 +61,34 @@ pub fn build_site(opts: &Options) -> Result<()> {
 
     source.map(&global_init)?;
 
-    if opts.parallel {
-        source.par_dir()
-    } else {
-        source.build_dir()
+    // feeds and the sitemap need absolute links, so a
+    // missing base url gets caught before anything is written
+    if opts.base_url.is_empty() {
+        if opts.sitemap {
+            return Err(MetaError::BaseUrl {
+                feature: "the sitemap".to_string(),
+            }
+            .into());
+        } else if !source.feed_dirs()?.is_empty() {
+            return Err(MetaError::BaseUrl {
+                feature: "feeds".to_string(),
+            }
+            .into());
+        }
     }
+
+    source.check_collisions()?;
+    let mut built = source.build_taxonomies()?;
+    source.build_redirects()?;
+
+    built.append(&mut if opts.parallel {
+        source.par_dir()?
+    } else {
+        source.build_dir()?
+    });
+
+    // the sitemap only lists pages that actually got written
+    build_sitemap(opts, &built)
 }
 
 pub fn single_file(opts: &Options) -> Result<String> {
@@ -76,18 +99,49 @@ pub fn single_file(opts: &Options) -> Result<String> {
         1
     );
 
-    let source = match fs::read_to_string(path) {
-        Ok(str) => Ok(str),
-        Err(_) => Err(eyre::Error::from(MetaError::FileNotFound {
-            path: path.to_string_lossy().to_string(),
-        })),
-    }?;
-
-    let mut file = parse_string(source, opts)?;
+    let mut file = MetaFile::build(path.clone(), opts)?;
 
     Ok(file.construct()?)
 }
 
+pub fn explain(opts: &Options) -> Result<String> {
+    let path = opts.file.as_ref().ok_or(MetaError::Unknown)?;
+    let key = opts.explain.as_ref().ok_or(MetaError::Unknown)?;
+
+    // default.meta definitions get merged in the same way a full build
+    // does it, so the definition marked as used is the one that gets used
+    let file = MetaFile::build_page(path.clone(), opts)?;
+
+    let mut output = String::new();
+    for (sigil, expansion) in [
+        ('$', Src::to_var(key)),
+        ('@', Src::to_arr(key)),
+        ('&', Src::to_pat(key)),
+    ] {
+        output.push_str(&format!("{sigil}{{{key}}}\n"));
+
+        let mut found = false;
+        for (scope, value) in file.explain(&expansion) {
+            let name = if scope.is_local() {
+                format!("*{scope}")
+            } else {
+                scope.to_string()
+            };
+
+            match value {
+                Some(value) if !found => {
+                    found = true;
+                    output.push_str(&format!("    {name} = {value} <= used\n"));
+                }
+                Some(value) => output.push_str(&format!("    {name} = {value}\n")),
+                None => output.push_str(&format!("    {name}\n")),
+            }
+        }
+    }
+
+    Ok(output)
+}
+
 pub fn new_site(opts: &Options) -> Result<()> {
     log!(
         opts,
@@ -5,7 +5,10 @@ fn main() -> eyre::Result<()> {
         return metaforge::new_site(&opts);
     }
 
-    if opts.file.is_some() {
+    if opts.explain.is_some() {
+        print!("{}", metaforge::explain(&opts)?);
+        Ok(())
+    } else if opts.file.is_some() {
         let str = metaforge::single_file(&opts)?;
         println!("{str}");
         Ok(())
@@ -11,8 +11,93 @@ pub use scope::*;
 #[cfg(test)]
 mod tests;
 
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime};
 use std::fmt::Display;
 
+// dates can be written as 2023-01-31, 2023-01-31 12:30:00, or in
+// rfc 3339 format. anything without a timezone is treated as utc
+pub fn parse_date(date: &str) -> Option<DateTime<FixedOffset>> {
+    let date = date.trim();
+
+    if let Ok(date) = DateTime::parse_from_rfc3339(date) {
+        Some(date)
+    } else if let Ok(date) = NaiveDateTime::parse_from_str(date, "%Y-%m-%d %H:%M:%S") {
+        Some(date.and_utc().fixed_offset())
+    } else {
+        NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .ok()
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .map(|date| date.and_utc().fixed_offset())
+    }
+}
+
+// escapes text for use in xml or html attributes
+pub fn escape_xml(str: &str) -> String {
+    str.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+// the name of a file without its extension, used as a fallback title
+pub fn file_stem(path: &std::path::Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+// everything in a directory, sorted since read_dir order is up to
+// the filesystem and builds and logs should be the same every time
+pub fn sorted_entries(dir: &std::path::Path) -> std::io::Result<Vec<std::path::PathBuf>> {
+    let mut paths = std::fs::read_dir(dir)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    paths.sort();
+    Ok(paths)
+}
+
+// turns text into an identifier the same way pandoc generates heading ids:
+// lowercase, whitespace becomes hyphens, anything that isn't alphanumeric,
+// an underscore, hyphen or period is dropped, and leading non-letters are removed
+pub fn slugify(text: &str) -> String {
+    text.chars()
+        .filter_map(|c| {
+            if c.is_whitespace() {
+                Some('-')
+            } else if c.is_alphanumeric() || c == '_' || c == '-' || c == '.' {
+                Some(c)
+            } else {
+                None
+            }
+        })
+        .flat_map(char::to_lowercase)
+        .skip_while(|c| !c.is_alphabetic())
+        .collect()
+}
+
+// turns text into a filename: lowercase, with anything that isn't alphanumeric,
+// an underscore or a hyphen replaced by a hyphen so nothing is dropped silently
+pub fn file_slug(text: &str) -> String {
+    let slug: String = text
+        .chars()
+        .flat_map(char::to_lowercase)
+        .map(|c| {
+            if c.is_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+
+    if slug.is_empty() {
+        String::from("-")
+    } else {
+        slug
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum Src {
     Str(String),
@@ -1,8 +1,19 @@
+mod collisions;
+mod feed;
 mod node;
 mod parallel;
+mod redirects;
+mod siblings;
+mod sitemap;
+mod taxonomy;
 
+// node and parallel only hold impl blocks for now, but stay
+// re-exported so anything made public there is reachable
+#[allow(unused_imports)]
 pub use node::*;
+#[allow(unused_imports)]
 pub use parallel::*;
+pub use sitemap::*;
 
 use crate::Options;
 use std::path::PathBuf;
@@ -0,0 +1,77 @@
+use crate::{log, DirNode, MetaError, Scope};
+use eyre::Result;
+use std::{collections::HashMap, path::PathBuf};
+
+impl<'a> DirNode<'a> {
+    // permalinks and aliases can send any page anywhere in the build directory,
+    // so every destination, generated or not, gets checked before anything is written
+    pub fn check_collisions(&self) -> Result<()> {
+        log!(self.opts, "checking for colliding output paths", 2);
+
+        // destinations, along with what builds to them
+        let mut dests: HashMap<PathBuf, String> = HashMap::new();
+        let mut register = |dest: PathBuf, from: String| -> Result<()> {
+            if let Some(first) = dests.get(&dest) {
+                return Err(MetaError::Collision {
+                    path: dest.to_string_lossy().to_string(),
+                    first: first.to_string(),
+                    second: from,
+                }
+                .into());
+            }
+
+            dests.insert(dest, from);
+            Ok(())
+        };
+
+        for page in self.global.collect_pages(&self.path, true)? {
+            let from = page.path.to_string_lossy().to_string();
+
+            for format in page.formats() {
+                for page in format.paginate()? {
+                    register(page.dest()?, from.clone())?;
+                }
+            }
+
+            for (dest, _) in page.aliases()? {
+                register(dest, from.clone())?;
+            }
+        }
+
+        // taxonomy pages get written before the rest of the site,
+        // so a source page in their place would replace them
+        for page in self.taxonomy_pages()? {
+            let taxonomy = page.get_variable("taxonomy")?;
+            // different terms can end up with the same filename
+            let from = match page.variables.get(&Scope::create_global("term")) {
+                Some(term) => format!("the {taxonomy} term '{term}'"),
+                None => format!("the {taxonomy} index"),
+            };
+            register(page.dest()?, from)?;
+        }
+
+        // everything else the build generates
+        for dir in self.feed_dirs()? {
+            let build_dir = self.opts.build.join(dir.strip_prefix(&self.opts.source)?);
+            let from = format!("the feed for {}", dir.display());
+            register(build_dir.join("feed.xml"), from.clone())?;
+            register(build_dir.join("atom.xml"), from)?;
+        }
+
+        if self.opts.sitemap {
+            register(
+                self.opts.build.join("sitemap.xml"),
+                "the sitemap".to_string(),
+            )?;
+        }
+
+        if self.opts.redirects {
+            register(
+                self.opts.build.join("_redirects"),
+                "the _redirects file".to_string(),
+            )?;
+        }
+
+        Ok(())
+    }
+}
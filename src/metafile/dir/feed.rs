@@ -0,0 +1,225 @@
+use crate::{escape_xml, log, parse_date, sorted_entries, DirNode, MetaError, MetaFile, Options};
+use chrono::{DateTime, FixedOffset, Utc};
+use eyre::Result;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+struct FeedItem {
+    title: String,
+    url: String,
+    date: Option<DateTime<FixedOffset>>,
+    modified: DateTime<FixedOffset>,
+    summary: String,
+    author: String,
+}
+
+impl<'a> DirNode<'a> {
+    // writes feed.xml (rss) and atom.xml for a directory with
+    // the feed header set in its default.meta
+    pub fn build_feed(&self) -> Result<()> {
+        if !self.global.header.feed {
+            return Ok(());
+        } else if self.opts.base_url.is_empty() {
+            return Err(MetaError::BaseUrl {
+                feature: "feeds".to_string(),
+            }
+            .into());
+        }
+
+        log!(
+            self.opts,
+            format!("building feeds for {}", self.path.display()),
+            1
+        );
+
+        // everything but the title is optional, so missing
+        // definitions shouldn't trip panic_undefined
+        let optional = |file: &MetaFile, key: &str| {
+            file.resolve(&file.variables, key)
+                .cloned()
+                .unwrap_or_default()
+        };
+
+        let mut items = Vec::new();
+        for page in self.global.collect_pages(&self.path, false)? {
+            let mut summary = optional(&page, "summary");
+            if summary.is_empty() {
+                summary = optional(&page, "description");
+            }
+
+            items.push(FeedItem {
+                title: page.get_variable("title")?,
+                url: format!("{}{}", self.opts.base_url, page.url()?),
+                date: parse_date(&optional(&page, "date")),
+                modified: modified(&page.path)?,
+                summary,
+                author: optional(&page, "author"),
+            });
+        }
+
+        // newest first, with undated items at the end
+        items.sort_by_key(|item| std::cmp::Reverse(item.date));
+
+        let dir = self.path.strip_prefix(&self.opts.source)?;
+        let url = if dir.as_os_str().is_empty() {
+            format!("{}/", self.opts.base_url)
+        } else {
+            format!("{}/{}/", self.opts.base_url, dir.to_string_lossy())
+        };
+
+        let mut title = optional(&self.global, "feed.title");
+        if title.is_empty() {
+            title = dir
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+        }
+        let description = optional(&self.global, "feed.description");
+
+        // atom feeds need an author, so fall back to the newest
+        // page with one, then to the title of the feed itself
+        let mut author = optional(&self.global, "feed.author");
+        if author.is_empty() {
+            author = items
+                .iter()
+                .map(|item| item.author.clone())
+                .find(|author| !author.is_empty())
+                .unwrap_or_else(|| title.clone());
+        }
+
+        // undated feeds are as new as their newest source file,
+        // so building the same files twice gives the same feed
+        let mut updated = modified(&self.global.path)?;
+        for item in items.iter() {
+            updated = updated.max(item.modified);
+        }
+        let updated = items.iter().find_map(|item| item.date).unwrap_or(updated);
+
+        let build_dir = self.opts.build.join(dir);
+        fs::create_dir_all(&build_dir)?;
+        fs::write(
+            build_dir.join("feed.xml"),
+            rss(&title, &description, &url, &items),
+        )?;
+        fs::write(
+            build_dir.join("atom.xml"),
+            atom(&title, &author, &url, updated, &items),
+        )?;
+
+        Ok(())
+    }
+
+    // every directory from this one down with the feed header set
+    // in its default.meta, checked before anything gets built
+    pub fn feed_dirs(&self) -> Result<Vec<PathBuf>> {
+        let mut dirs = Vec::new();
+        feed_dirs(self.opts, &self.path, &mut dirs)?;
+        Ok(dirs)
+    }
+}
+
+fn feed_dirs(opts: &Options, dir: &Path, dirs: &mut Vec<PathBuf>) -> Result<()> {
+    let global = dir.join("default.meta");
+    if global.exists() && MetaFile::build(global, opts)?.header.feed {
+        dirs.push(dir.to_path_buf());
+    }
+
+    for path in sorted_entries(dir)? {
+        if path.is_dir() {
+            feed_dirs(opts, &path, dirs)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn modified(path: &Path) -> Result<DateTime<FixedOffset>> {
+    let modified: DateTime<Utc> = fs::metadata(path)?.modified()?.into();
+    Ok(modified.fixed_offset())
+}
+
+fn rss(title: &str, description: &str, url: &str, items: &[FeedItem]) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<rss version=\"2.0\">\n<channel>\n");
+    xml.push_str(&format!("<title>{}</title>\n", escape_xml(title)));
+    xml.push_str(&format!("<link>{}</link>\n", escape_xml(url)));
+    xml.push_str(&format!(
+        "<description>{}</description>\n",
+        escape_xml(description)
+    ));
+
+    for item in items {
+        xml.push_str("<item>\n");
+        xml.push_str(&format!("<title>{}</title>\n", escape_xml(&item.title)));
+        xml.push_str(&format!("<link>{}</link>\n", escape_xml(&item.url)));
+        xml.push_str(&format!("<guid>{}</guid>\n", escape_xml(&item.url)));
+        if let Some(date) = item.date {
+            xml.push_str(&format!("<pubDate>{}</pubDate>\n", date.to_rfc2822()));
+        }
+        if !item.summary.is_empty() {
+            xml.push_str(&format!(
+                "<description>{}</description>\n",
+                escape_xml(&item.summary)
+            ));
+        }
+        xml.push_str("</item>\n");
+    }
+
+    xml.push_str("</channel>\n</rss>\n");
+    xml
+}
+
+fn atom(
+    title: &str,
+    author: &str,
+    url: &str,
+    updated: DateTime<FixedOffset>,
+    items: &[FeedItem],
+) -> String {
+    let updated = updated.to_rfc3339();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    xml.push_str(&format!("<title>{}</title>\n", escape_xml(title)));
+    xml.push_str(&format!("<link href=\"{}\"/>\n", escape_xml(url)));
+    xml.push_str(&format!(
+        "<link rel=\"self\" href=\"{}atom.xml\"/>\n",
+        escape_xml(url)
+    ));
+    xml.push_str(&format!("<id>{}</id>\n", escape_xml(url)));
+    xml.push_str(&format!("<updated>{updated}</updated>\n"));
+    xml.push_str(&format!(
+        "<author>\n<name>{}</name>\n</author>\n",
+        escape_xml(author)
+    ));
+
+    for item in items {
+        // atom requires an updated date for everything, so
+        // undated items use when their source was last changed
+        let date = item.date.unwrap_or(item.modified).to_rfc3339();
+
+        xml.push_str("<entry>\n");
+        xml.push_str(&format!("<title>{}</title>\n", escape_xml(&item.title)));
+        xml.push_str(&format!("<link href=\"{}\"/>\n", escape_xml(&item.url)));
+        xml.push_str(&format!("<id>{}</id>\n", escape_xml(&item.url)));
+        xml.push_str(&format!("<updated>{date}</updated>\n"));
+        if !item.author.is_empty() {
+            xml.push_str(&format!(
+                "<author>\n<name>{}</name>\n</author>\n",
+                escape_xml(&item.author)
+            ));
+        }
+        if !item.summary.is_empty() {
+            xml.push_str(&format!(
+                "<summary>{}</summary>\n",
+                escape_xml(&item.summary)
+            ));
+        }
+        xml.push_str("</entry>\n");
+    }
+
+    xml.push_str("</feed>\n");
+    xml
+}
@@ -1,26 +1,9 @@
 use crate::{error::*, Options};
 use eyre::Result;
-use minify_html::{minify, Cfg};
 use std::{fs, path::PathBuf};
 
 use super::*;
 
-const HTML_CFG: Cfg = Cfg {
-    do_not_minify_doctype: false,
-    ensure_spec_compliant_unquoted_attribute_values: false,
-    keep_closing_tags: true,
-    keep_html_and_head_opening_tags: true,
-    keep_spaces_between_attributes: false,
-    keep_comments: false,
-    minify_css: true,
-    minify_css_level_1: false,
-    minify_css_level_2: true,
-    minify_css_level_3: false,
-    minify_js: true,
-    remove_bangs: true,
-    remove_processing_instructions: true,
-};
-
 impl<'a> DirNode<'a> {
     pub fn build(path: PathBuf, opts: &'a Options) -> Result<Self> {
         assert!(path.is_dir() && path.exists());
@@ -46,7 +29,7 @@ impl<'a> DirNode<'a> {
 
     // parses all contained files and directories and pushes
     // parsed structures into the files and directories vectors
-    pub fn map(&mut self, global: &'a MetaFile) -> Result<()> {
+    pub fn map(&mut self, global: &MetaFile<'a>) -> Result<()> {
         if self.path.join("default.meta").exists() {
             if let Some(mut new_global) = check_ignore(MetaFile::build(
                 self.path.clone().join("default.meta"),
@@ -57,9 +40,7 @@ impl<'a> DirNode<'a> {
             }
         }
 
-        for f in fs::read_dir(&self.path)? {
-            let file = f?.path();
-
+        for file in sorted_entries(&self.path)? {
             if self.global.header.copy_only {
                 let dest = self.global.dest()?;
                 fs::copy(file, dest.parent().unwrap_or(&self.opts.build))?;
@@ -81,17 +62,18 @@ impl<'a> DirNode<'a> {
         Ok(())
     }
 
-    pub fn build_files(&mut self) -> Result<()> {
+    pub fn build_files(&mut self) -> Result<Vec<BuiltPage>> {
+        // everything gets merged first so sibling links
+        // see any globals their permalinks use
         for file in self.files.iter_mut() {
             file.merge(&self.global);
-            match file.construct() {
-                Ok(str) => {
-                    if file.header.minify && &file.header.filetype == "html" {
-                        fs::write(file.dest()?, minify(str.as_bytes(), &HTML_CFG))?;
-                    } else {
-                        fs::write(file.dest()?, str)?;
-                    }
-                }
+        }
+        self.link_siblings()?;
+
+        let mut built = Vec::new();
+        for file in self.files.iter_mut() {
+            match file.render() {
+                Ok(mut pages) => built.append(&mut pages),
                 Err(e) => {
                     // print a line to stderr about failure but continue with other files
                     if self.opts.force {
@@ -109,17 +91,19 @@ impl<'a> DirNode<'a> {
                 }
             }
         }
-        Ok(())
+        Ok(built)
     }
 
-    pub fn build_dir(&'a mut self) -> Result<()> {
-        self.build_files()?;
+    // builds every file below this directory, returning the pages written
+    pub fn build_dir(&'a mut self) -> Result<Vec<BuiltPage>> {
+        let mut built = self.build_files()?;
+        self.build_feed()?;
 
         for dir in self.dirs.iter_mut() {
             dir.map(&self.global)?;
-            dir.build_dir()?;
+            built.append(&mut dir.build_dir()?);
         }
 
-        Ok(())
+        Ok(built)
     }
 }
@@ -1,43 +1,55 @@
-use crate::{DirNode, MetaError};
+use crate::{BuiltPage, DirNode, MetaError};
 use eyre::Result;
 use rayon::prelude::*;
-use std::fs;
 
 impl<'a> DirNode<'a> {
-    pub fn par_file(&mut self) -> Result<()> {
-        self.files.par_iter_mut().for_each(|file| {
+    pub fn par_file(&mut self) -> Result<Vec<BuiltPage>> {
+        for file in self.files.iter_mut() {
             file.merge(&self.global);
-            match file.construct() {
-                Ok(str) => {
-                    fs::write(file.dest().unwrap(), str).unwrap();
-                }
-                Err(e) => {
-                    // print a line to stderr about failure but continue with other files
-                    if self.opts.force {
-                        eprintln!("ignoring {}: {}", file.path.display(), e);
-                    } else {
-                        match *e {
-                            MetaError::Ignored => {}
-                            e => {
-                                eprintln!("{}", file.path.display());
-                                panic!("{}", e);
+        }
+        self.link_siblings()?;
+
+        let built = self
+            .files
+            .par_iter_mut()
+            .flat_map(|file| {
+                match file.render() {
+                    Ok(pages) => pages,
+                    Err(e) => {
+                        // print a line to stderr about failure but continue with other files
+                        if self.opts.force {
+                            eprintln!("ignoring {}: {}", file.path.display(), e);
+                        } else {
+                            match *e {
+                                MetaError::Ignored => {}
+                                e => {
+                                    eprintln!("{}", file.path.display());
+                                    panic!("{}", e);
+                                }
                             }
                         }
+                        Vec::new()
                     }
                 }
-            }
-        });
-        Ok(())
+            })
+            .collect();
+        Ok(built)
     }
 
-    pub fn par_dir(&'a mut self) -> Result<()> {
-        self.build_files()?;
+    pub fn par_dir(&'a mut self) -> Result<Vec<BuiltPage>> {
+        let mut built = self.build_files()?;
+        self.build_feed()?;
 
-        self.dirs.par_iter_mut().for_each(|dir| {
-            dir.map(&self.global).unwrap();
-            dir.build_dir().unwrap();
-        });
+        let dirs: Vec<Vec<BuiltPage>> = self
+            .dirs
+            .par_iter_mut()
+            .map(|dir| {
+                dir.map(&self.global).unwrap();
+                dir.build_dir().unwrap()
+            })
+            .collect();
 
-        Ok(())
+        built.extend(dirs.into_iter().flatten());
+        Ok(built)
     }
 }
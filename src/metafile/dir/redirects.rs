@@ -0,0 +1,27 @@
+use crate::{log, DirNode};
+use eyre::Result;
+use std::fs;
+
+impl<'a> DirNode<'a> {
+    // writes every alias to a _redirects file in the build directory,
+    // for hosts that handle redirects themselves
+    pub fn build_redirects(&self) -> Result<()> {
+        if !self.opts.redirects {
+            return Ok(());
+        }
+
+        log!(self.opts, "building _redirects", 1);
+
+        let mut redirects = String::new();
+        for page in self.global.collect_pages(&self.path, true)? {
+            for (_, alias) in page.aliases()? {
+                redirects.push_str(&format!("{alias} {} 301\n", page.url()?));
+            }
+        }
+
+        fs::create_dir_all(&self.opts.build)?;
+        fs::write(self.opts.build.join("_redirects"), redirects)?;
+
+        Ok(())
+    }
+}
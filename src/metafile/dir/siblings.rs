@@ -0,0 +1,47 @@
+use crate::{file_stem, DirNode, MetaFile, Scope};
+use eyre::Result;
+
+impl<'a> DirNode<'a> {
+    // defines PREV.url, PREV.title, NEXT.url and NEXT.title in each file,
+    // linking files in the same directory by weight, then filename
+    pub fn link_siblings(&mut self) -> Result<()> {
+        let mut order: Vec<usize> = (0..self.files.len())
+            .filter(|i| self.files[*i].is_listed())
+            .collect();
+        order.sort_by_key(|i| (self.files[*i].header.weight, self.files[*i].path.clone()));
+
+        let links = order
+            .iter()
+            .map(|i| {
+                let file = &self.files[*i];
+                Ok((file.url()?, sibling_title(file)))
+            })
+            .collect::<Result<Vec<(String, String)>>>()?;
+
+        for (pos, i) in order.iter().enumerate() {
+            let prev = pos.checked_sub(1).and_then(|pos| links.get(pos));
+            let next = links.get(pos + 1);
+
+            for (name, link) in [("PREV", prev), ("NEXT", next)] {
+                let (url, title) = link.cloned().unwrap_or_default();
+
+                // anything the file defines itself takes precedence
+                for (field, val) in [("url", url), ("title", title)] {
+                    self.files[*i]
+                        .variables
+                        .entry(Scope::create_global(format!("{name}.{field}")))
+                        .or_insert(val);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn sibling_title(file: &MetaFile) -> String {
+    match file.resolve(&file.variables, "title") {
+        Some(title) => title.to_string(),
+        None => file_stem(&file.path),
+    }
+}
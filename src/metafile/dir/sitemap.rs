@@ -0,0 +1,52 @@
+use crate::{escape_xml, log, BuiltPage, MetaError, Options};
+use chrono::{DateTime, Utc};
+use eyre::Result;
+use std::fs;
+
+// writes sitemap.xml to the root of the build directory once the site is
+// built, listing every html page that was written and doesn't opt out
+pub fn build_sitemap(opts: &Options, built: &[BuiltPage]) -> Result<()> {
+    if !opts.sitemap {
+        return Ok(());
+    } else if opts.base_url.is_empty() {
+        return Err(MetaError::BaseUrl {
+            feature: "the sitemap".to_string(),
+        }
+        .into());
+    }
+
+    log!(opts, "building sitemap", 1);
+
+    // parallel builds can finish in any order
+    let mut pages: Vec<&BuiltPage> = built.iter().filter(|page| page.sitemap).collect();
+    pages.sort_by(|a, b| a.url.cmp(&b.url));
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n");
+
+    for page in pages {
+        xml.push_str("<url>\n");
+        xml.push_str(&format!(
+            "<loc>{}</loc>\n",
+            escape_xml(&format!("{}{}", opts.base_url, page.url))
+        ));
+
+        // generated pages like taxonomies don't have a source file
+        if let Ok(modified) = fs::metadata(&page.source).and_then(|meta| meta.modified()) {
+            let lastmod: DateTime<Utc> = modified.into();
+            xml.push_str(&format!(
+                "<lastmod>{}</lastmod>\n",
+                lastmod.format("%Y-%m-%d")
+            ));
+        }
+
+        xml.push_str("</url>\n");
+    }
+
+    xml.push_str("</urlset>\n");
+
+    fs::create_dir_all(&opts.build)?;
+    fs::write(opts.build.join("sitemap.xml"), xml)?;
+
+    Ok(())
+}
@@ -0,0 +1,116 @@
+use crate::{file_slug, log, BuiltPage, DirNode, MetaFile, Scope, Src};
+use eyre::Result;
+use std::collections::BTreeMap;
+
+impl<'a> DirNode<'a> {
+    // builds a listing page for every term used in each taxonomy array,
+    // along with an index page listing the terms themselves
+    pub fn build_taxonomies(&self) -> Result<Vec<BuiltPage>> {
+        if !self.opts.taxonomies.is_empty() {
+            log!(self.opts, "building taxonomies", 1);
+        }
+
+        let mut built = Vec::new();
+        for mut page in self.taxonomy_pages()? {
+            let output = page.construct()?;
+            page.write(&output)?;
+            built.push(page.built()?);
+        }
+
+        Ok(built)
+    }
+
+    // the listing pages for every taxonomy, ready to be built. they get
+    // made up front so their destinations can be checked for collisions
+    pub fn taxonomy_pages(&self) -> Result<Vec<MetaFile<'a>>> {
+        let mut taxonomy_pages = Vec::new();
+        if self.opts.taxonomies.is_empty() {
+            return Ok(taxonomy_pages);
+        }
+
+        let pages = self.global.collect_pages(&self.path, true)?;
+
+        for taxonomy in self.opts.taxonomies.iter() {
+            log!(self.opts, format!("collecting terms for {taxonomy}"), 2);
+
+            // BTreeMap keeps terms sorted for the index page
+            let mut terms: BTreeMap<String, Vec<&MetaFile>> = BTreeMap::new();
+            for page in pages.iter() {
+                if let Some(values) = page.resolve(&page.arrays, taxonomy) {
+                    for term in values {
+                        terms.entry(term.to_string()).or_default().push(page);
+                    }
+                }
+            }
+
+            let mut term_titles = Vec::new();
+            let mut term_urls = Vec::new();
+            let mut term_counts = Vec::new();
+
+            for (term, listed) in terms.iter() {
+                let mut page = self.taxonomy_page(taxonomy, &file_slug(term), "taxonomy");
+                page.variables
+                    .insert(Scope::create_global("term"), term.to_string());
+                page.variables
+                    .insert(Scope::create_global("title"), term.to_string());
+
+                for field in ["title", "url", "date"] {
+                    let values = listed
+                        .iter()
+                        .map(|listed| listed.get_field(field))
+                        .collect::<Result<Vec<String>>>()?;
+                    page.arrays
+                        .insert(Scope::create_global(format!("taxonomy.{field}")), values);
+                }
+
+                page.merge(&self.global);
+
+                term_titles.push(term.to_string());
+                term_urls.push(page.url()?);
+                term_counts.push(listed.len().to_string());
+                taxonomy_pages.push(page);
+            }
+
+            let mut index = self.taxonomy_page(taxonomy, "index", "taxonomy.index");
+            index
+                .variables
+                .insert(Scope::create_global("title"), taxonomy.to_string());
+            index
+                .arrays
+                .insert(Scope::create_global("taxonomy.title"), term_titles);
+            index
+                .arrays
+                .insert(Scope::create_global("taxonomy.url"), term_urls);
+            index
+                .arrays
+                .insert(Scope::create_global("taxonomy.count"), term_counts);
+
+            index.merge(&self.global);
+            taxonomy_pages.push(index);
+        }
+
+        Ok(taxonomy_pages)
+    }
+
+    // taxonomy pages don't exist in the source directory, so they get
+    // built in memory as a file that only expands the given pattern
+    fn taxonomy_page(&self, taxonomy: &str, name: &str, pattern: &str) -> MetaFile<'a> {
+        let mut page = MetaFile::new(self.opts);
+        page.path = self.opts.source.join(taxonomy).join(format!("{name}.meta"));
+        page.header.pandoc = Some(false);
+        page.source = vec![Src::to_pat(pattern)];
+
+        page.variables
+            .insert(Scope::create_global("taxonomy"), taxonomy.to_string());
+
+        // pattern/taxonomy/[NAME].meta gets used instead of the
+        // default pattern for a taxonomy if it exists
+        let custom = format!("{}.meta", pattern.replace('.', "/") + "/" + taxonomy);
+        if self.opts.pattern.join(custom).exists() {
+            page.patterns
+                .insert(Scope::create_local(pattern), taxonomy.to_string());
+        }
+
+        page
+    }
+}
@@ -1,16 +1,46 @@
+mod aliases;
 mod arrays;
 mod attributes;
+mod binary;
+mod breadcrumbs;
+mod nav;
+mod pages;
+mod paginate;
 mod patterns;
+mod raw;
+mod resolve;
 mod source;
+mod toc;
 mod variables;
 
 use crate::{log, parse_string, MetaError, Options};
 use eyre::Result;
+use minify_html::{minify, Cfg};
 use pandoc::{InputFormat, InputKind, OutputFormat, OutputKind, Pandoc};
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::{Arc, OnceLock},
+};
 
 use super::*;
 
+const HTML_CFG: Cfg = Cfg {
+    do_not_minify_doctype: false,
+    ensure_spec_compliant_unquoted_attribute_values: false,
+    keep_closing_tags: true,
+    keep_html_and_head_opening_tags: true,
+    keep_spaces_between_attributes: false,
+    keep_comments: false,
+    minify_css: true,
+    minify_css_level_1: false,
+    minify_css_level_2: true,
+    minify_css_level_3: false,
+    minify_js: true,
+    remove_bangs: true,
+    remove_processing_instructions: true,
+};
+
 #[derive(Debug, Clone)]
 pub struct MetaFile<'a> {
     pub opts: &'a Options,
@@ -20,6 +50,26 @@ pub struct MetaFile<'a> {
     pub arrays: HashMap<Scope, Vec<String>>,
     pub patterns: HashMap<Scope, String>,
     pub source: Vec<Src>,
+    pub stack: Vec<PathBuf>,
+    pub pagination: Option<Pagination>,
+    // the nav tree, built the first time a page asks for it
+    pub nav: Arc<OnceLock<String>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pagination {
+    pub collection: String,
+    pub per_page: usize,
+    pub current: usize,
+    pub total: usize,
+}
+
+// a page that got written to the build directory
+#[derive(Debug, Clone, PartialEq)]
+pub struct BuiltPage {
+    pub url: String,
+    pub source: PathBuf,
+    pub sitemap: bool,
 }
 
 impl<'a> MetaFile<'a> {
@@ -32,6 +82,9 @@ impl<'a> MetaFile<'a> {
             arrays: HashMap::new(),
             patterns: HashMap::new(),
             source: Vec::new(),
+            stack: Vec::new(),
+            pagination: None,
+            nav: Arc::new(OnceLock::new()),
         }
     }
 
@@ -54,6 +107,26 @@ impl<'a> MetaFile<'a> {
         Ok(metafile)
     }
 
+    // builds a file in the source directory along with the
+    // definitions from every default.meta above it
+    pub fn build_page(path: PathBuf, opts: &'a Options) -> Result<Self, MetaError> {
+        let mut page = MetaFile::build(path.clone(), opts)?;
+
+        // closer directories get merged first so they take precedence
+        for dir in path.ancestors().skip(1) {
+            if !dir.starts_with(&opts.source) {
+                break;
+            }
+
+            let global = dir.join("default.meta");
+            if global.exists() && global != path {
+                page.merge(&MetaFile::build(global, opts)?);
+            }
+        }
+
+        Ok(page)
+    }
+
     pub fn construct(&mut self) -> Result<String, Box<MetaError>> {
         log!(self.opts, format!("building {}", self.path.display()), 1);
 
@@ -61,6 +134,13 @@ impl<'a> MetaFile<'a> {
             return Ok(String::new());
         } else if self.header.ignore {
             return Err(Box::new(MetaError::Ignored));
+        } else if self.is_unpublished() {
+            log!(
+                self.opts,
+                format!("skipping unpublished {}", self.path.display()),
+                1
+            );
+            return Err(Box::new(MetaError::Ignored));
         }
 
         if self.header.copy_only {
@@ -70,12 +150,17 @@ impl<'a> MetaFile<'a> {
             return Err(Box::new(MetaError::Ignored));
         }
 
-        let src_str = if self.header.pandoc.map_or(true, |x| x) {
+        // binary output is converted after the base pattern instead
+        let src_str = if self.header.pandoc.is_none_or(|x| x) && !self.is_binary() {
             self.pandoc().map_err(MetaError::from)
         } else {
             self.get_source().map_err(MetaError::from)
         }?;
 
+        // the source is converted now, so its headings are known
+        let toc = self.toc(&src_str);
+        let src_str = patterns::replace_placeholder(&src_str, toc::TOC_MARK, &toc);
+
         let pattern = self.get_pattern("base").map_err(MetaError::from)?;
         let mut base = parse_string(pattern, self.opts).map_err(|e| MetaError::ParserError {
             file: self.path.to_string_lossy().to_string(),
@@ -83,22 +168,75 @@ impl<'a> MetaFile<'a> {
         })?;
 
         base.merge(self);
+        base.header.filetype = self.header.filetype.clone();
+        base.stack = vec![self.path.clone()];
+        base.pagination = self.pagination.clone();
         base.patterns
             .insert(Scope::create_global("SOURCE"), src_str);
-        let mut base_path = self.opts.pattern.join("base").join(
-            self.patterns
-                .get(&Scope::create_global("base"))
-                .unwrap_or(&"default".into()),
-        );
-
-        base_path.set_extension("meta");
-        base.path = base_path;
+        base.patterns.insert(Scope::create_global("TOC"), toc);
+        if let Some(base_path) = self.pattern_path("base").map_err(MetaError::from)? {
+            base.path = base_path;
+        }
 
         let output = base.get_source().map_err(MetaError::from)?;
 
         Ok(output)
     }
 
+    // constructs and writes every output file for a source file,
+    // returning the pages that got written
+    pub fn render(&mut self) -> Result<Vec<BuiltPage>, Box<MetaError>> {
+        let mut built = Vec::new();
+
+        for file in self.formats() {
+            for mut page in file.paginate().map_err(MetaError::from)? {
+                if page.is_binary() {
+                    let output = page.construct_bytes()?;
+                    page.write_bytes(&output).map_err(MetaError::from)?;
+                } else {
+                    let output = page.construct()?;
+                    page.write(&output).map_err(MetaError::from)?;
+                }
+
+                built.push(page.built().map_err(MetaError::from)?);
+            }
+        }
+
+        self.write_aliases().map_err(MetaError::from)?;
+
+        Ok(built)
+    }
+
+    // only html pages that don't opt out go in the sitemap
+    pub fn built(&self) -> Result<BuiltPage> {
+        Ok(BuiltPage {
+            url: self.url()?,
+            source: self.path.clone(),
+            sitemap: self.header.sitemap && self.header.filetype == "html",
+        })
+    }
+
+    // writes constructed output to the destination of the file,
+    // minifying html unless the header says otherwise
+    pub fn write(&self, output: &str) -> Result<()> {
+        if self.opts.minify && self.header.minify && &self.header.filetype == "html" {
+            self.write_bytes(&minify(output.as_bytes(), &HTML_CFG))
+        } else {
+            self.write_bytes(output.as_bytes())
+        }
+    }
+
+    pub fn write_bytes(&self, output: &[u8]) -> Result<()> {
+        let dest = self.dest()?;
+        if let Some(dir) = dest.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+
+        std::fs::write(dest, output)?;
+
+        Ok(())
+    }
+
     pub fn merge(&mut self, other: &Self) {
         macro_rules! merge (
             ($m:ident) => {
@@ -113,5 +251,15 @@ impl<'a> MetaFile<'a> {
         merge!(variables);
         merge!(arrays);
         merge!(patterns);
+
+        // the nav tree is the same for the whole site, so everything
+        // merged from the same global file shares one copy of it
+        self.nav = other.nav.clone();
+
+        // pretty_urls is set per directory, so it gets
+        // passed down along with the definitions
+        if self.header.pretty_urls.is_none() {
+            self.header.pretty_urls = other.header.pretty_urls;
+        }
     }
 }
@@ -0,0 +1,69 @@
+use super::*;
+
+impl<'a> MetaFile<'a> {
+    // the build path and url of each entry in the aliases header
+    pub fn aliases(&self) -> Result<Vec<(PathBuf, String)>> {
+        let mut aliases = Vec::new();
+
+        for alias in self.header.aliases.iter() {
+            let dest = self.link_path(alias, "html")?;
+            let mut url = format!(
+                "/{}",
+                dest.strip_prefix(&self.opts.build)?.to_string_lossy()
+            );
+
+            if alias.ends_with('/') {
+                url.truncate(url.len() - "index.html".len());
+            }
+
+            aliases.push((dest, url));
+        }
+
+        Ok(aliases)
+    }
+
+    // writes a page at each alias that sends visitors on to this file
+    pub fn write_aliases(&self) -> Result<()> {
+        if self.header.aliases.is_empty() {
+            return Ok(());
+        }
+
+        let url = escape_xml(&self.url()?);
+        let canonical = escape_xml(&format!("{}{}", self.opts.base_url, self.url()?));
+
+        let page = format!(
+            "<!DOCTYPE html>
+<html>
+<head>
+<meta charset=\"utf-8\">
+<title>Redirecting to {url}</title>
+<link rel=\"canonical\" href=\"{canonical}\">
+<meta http-equiv=\"refresh\" content=\"0; url={url}\">
+</head>
+<body>
+<p>moved to <a href=\"{url}\">{url}</a></p>
+</body>
+</html>
+"
+        );
+
+        for (dest, _) in self.aliases()? {
+            log!(
+                self.opts,
+                format!(
+                    "writing alias {} for {}",
+                    dest.display(),
+                    self.path.display()
+                ),
+                2
+            );
+
+            if let Some(dir) = dest.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            std::fs::write(dest, &page)?;
+        }
+
+        Ok(())
+    }
+}
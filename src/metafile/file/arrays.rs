@@ -8,51 +8,35 @@ impl<'a> MetaFile<'a> {
             2
         );
 
-        let map: HashMap<String, &[String]> = self
-            .source
-            .iter()
-            // filter out arrays from source vec
-            .filter_map(|x| {
-                if let Src::Arr(array) = x {
-                    Some(array)
-                } else {
-                    None
-                }
-            })
-            // make a hash map of [keys in source] -> [defined arrays]
-            .map(|key| {
-                // concat array to pattern name to get key in HashMap
-                let class = self.class().unwrap_or_default();
-                let class_key = Scope::Local(class + "." + key);
-                let name = self.name().unwrap_or_default();
-                let name_key = Scope::Local(name + "." + key);
-
-                let value = if let Some(val) = self.arrays.get(&name_key) {
-                    &val[..]
-                } else if let Some(val) = self.arrays.get(&name_key.global()) {
-                    &val[..]
-                } else if let Some(val) = self.arrays.get(&class_key) {
-                    &val[..]
-                } else if let Some(val) = self.arrays.get(&class_key.global()) {
-                    &val[..]
-                } else if let Some(val) = self.arrays.get(&Scope::create_global(key)) {
-                    &val[..]
-                } else if let Some(val) = self.arrays.get(&Scope::create_local(key)) {
-                    &val[..]
-                } else if self.opts.undefined {
-                    panic!(
-                        "{}",
-                        MetaError::UndefinedExpand {
-                            val: key.to_string(),
-                            path: self.path.to_string_lossy().to_string(),
-                        }
-                    )
-                } else {
-                    &[]
-                };
-                (key.to_string(), value)
-            })
-            .collect();
+        // make a hash map of [keys in source] -> [defined arrays]
+        let mut map: HashMap<String, Vec<String>> = HashMap::new();
+        // filter out arrays from source vec
+        for key in self.source.iter().filter_map(|x| {
+            if let Src::Arr(array) = x {
+                Some(array)
+            } else {
+                None
+            }
+        }) {
+            let value = if let Some(pages) = self.get_collection(key)? {
+                pages
+            } else if let Some(crumbs) = self.get_breadcrumbs(key)? {
+                crumbs
+            } else if let Some(val) = self.resolve(&self.arrays, key) {
+                val.clone()
+            } else if self.opts.undefined {
+                panic!(
+                    "{}",
+                    MetaError::UndefinedExpand {
+                        val: key.to_string(),
+                        path: self.path.to_string_lossy().to_string(),
+                    }
+                )
+            } else {
+                Vec::new()
+            };
+            map.insert(key.to_string(), value);
+        }
 
         // loop to duplicate the output template for each array member
         let mut expanded = String::new();
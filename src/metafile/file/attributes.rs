@@ -2,20 +2,158 @@ use super::*;
 
 impl<'a> MetaFile<'a> {
     pub fn dest(&self) -> Result<PathBuf> {
-        let mut path = self
-            .opts
-            .build
-            .join(self.path.strip_prefix(&self.opts.source)?);
-        path.set_extension(&self.header.filetype);
+        let mut path = if self.header.permalink.is_empty() {
+            let mut path = self
+                .opts
+                .build
+                .join(self.path.strip_prefix(&self.opts.source)?);
+            path.set_extension(&self.header.filetype);
+
+            // pretty urls build foo.meta to foo/index.html
+            let index = path.file_stem().is_some_and(|stem| stem == "index");
+            if self.pretty_urls() && !index {
+                path = path.with_extension("").join("index.html");
+            }
+
+            path
+        } else {
+            self.link_path(&self.header.permalink, &self.header.filetype)?
+        };
+
+        // pages after the first go in page/[N]/ next to an index page,
+        // or in [stem]/page/[N]/ for anything else, so paginated files
+        // in the same directory don't build over each other
+        if let Some(pagination) = &self.pagination {
+            if pagination.current > 1 {
+                let mut dir = path.parent().unwrap_or(&self.opts.build).to_path_buf();
+                if let Some(stem) = path.file_stem().filter(|stem| *stem != "index") {
+                    dir = dir.join(stem);
+                }
+                path = dir
+                    .join("page")
+                    .join(pagination.current.to_string())
+                    .join("index")
+                    .with_extension(&self.header.filetype);
+            }
+        }
 
         Ok(path)
     }
 
+    // permalinks and aliases are relative to the build directory, with ${key}
+    // replaced by variables from the file. a trailing slash builds to
+    // index.[filetype], and a missing extension gets filled in
+    pub fn link_path(&self, link: &str, filetype: &str) -> Result<PathBuf> {
+        let invalid = || MetaError::Header {
+            opt: link.to_string(),
+        };
+
+        let template = link;
+        let mut link = String::new();
+        let mut rest = template;
+        while let Some(start) = rest.find("${") {
+            link.push_str(&rest[..start]);
+            rest = &rest[start + 2..];
+
+            let end = rest.find('}').ok_or_else(invalid)?;
+            link.push_str(&self.get_variable(rest[..end].trim())?);
+            rest = &rest[end + 1..];
+        }
+        link.push_str(rest);
+
+        let mut path = self.opts.build.join(link.trim_start_matches('/'));
+        if link.ends_with('/') {
+            path = path.join("index").with_extension(filetype);
+        } else if path.extension().is_none() {
+            path.set_extension(filetype);
+        }
+
+        // links can't point outside the build directory
+        if path
+            .components()
+            .any(|c| c == std::path::Component::ParentDir)
+        {
+            return Err(invalid().into());
+        }
+
+        Ok(path)
+    }
+
+    // a copy of the file for each output format in the filetype header
+    pub fn formats(&self) -> Vec<MetaFile<'a>> {
+        if self.header.formats.is_empty() {
+            return vec![self.clone()];
+        }
+
+        self.header
+            .formats
+            .iter()
+            .map(|format| {
+                let mut file = self.clone();
+                file.header.filetype = format.to_string();
+                file
+            })
+            .collect()
+    }
+
+    // the path of the built file from the root of the site
+    pub fn url(&self) -> Result<String> {
+        let dest = self.dest()?;
+        let path = dest.strip_prefix(&self.opts.build)?;
+        let url = format!("/{}", path.to_string_lossy());
+
+        // pretty urls leave index.html off the end, and so
+        // do permalinks that were written as a directory
+        if self.pretty_urls() || self.header.permalink.ends_with('/') {
+            if let Some(dir) = url.strip_suffix("index.html") {
+                return Ok(dir.to_string());
+            }
+        }
+
+        Ok(url)
+    }
+
+    // set with --pretty-urls, or the pretty_urls header for a single
+    // file or directory. only html output gets pretty urls
+    pub fn pretty_urls(&self) -> bool {
+        self.header.pretty_urls.unwrap_or(self.opts.pretty_urls)
+            && self.header.filetype == "html"
+            && !self.header.copy_only
+    }
+
+    // the url of the source file being built, which is at the
+    // bottom of the stack when called from inside a pattern
+    pub fn current_url(&self) -> Result<String> {
+        let current = self.stack.first().unwrap_or(&self.path);
+        if current == &self.path {
+            return self.url();
+        }
+
+        // the source file is needed for its permalink, but pages
+        // that only exist in memory just have their path
+        let mut page = if current.exists() {
+            MetaFile::build_page(current.clone(), self.opts)?
+        } else {
+            let mut page = MetaFile::new(self.opts);
+            page.path = current.clone();
+            page
+        };
+        page.header.filetype = self.header.filetype.clone();
+        page.pagination = self.pagination.clone();
+
+        page.url()
+    }
+
     pub fn class(&self) -> Result<String> {
-        // only care about classes in the pattern dir
-        self.path
-            .strip_prefix(&self.opts.pattern)?
-            .parent()
+        // patterns are classed by their pattern directory,
+        // and source files by their source directory
+        let path = if self.path.starts_with(&self.opts.pattern) {
+            self.path.strip_prefix(&self.opts.pattern)?
+        } else {
+            self.path.strip_prefix(&self.opts.source)?
+        };
+
+        path.parent()
             .map(|s| s.to_string_lossy().to_string().replace('/', "."))
             .ok_or(
                 MetaError::Name {
@@ -0,0 +1,56 @@
+use super::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// pandoc won't write binary formats to a pipe, so they go through
+// a temporary file, numbered so parallel builds don't collide
+static TEMP_FILES: AtomicUsize = AtomicUsize::new(0);
+
+impl<'a> MetaFile<'a> {
+    // formats that pandoc outputs as bytes instead of text
+    pub fn is_binary(&self) -> bool {
+        matches!(&self.header.filetype[..], "pdf" | "docx" | "epub" | "odt")
+    }
+
+    // binary files get built as text in the source format, base pattern
+    // included, then converted all at once by pandoc
+    pub fn construct_bytes(&mut self) -> Result<Vec<u8>, Box<MetaError>> {
+        // fail before expanding anything, unless the file gets skipped anyway
+        let skipped = self.header.ignore || self.header.copy_only || self.is_unpublished();
+        if !skipped && (self.opts.no_pandoc || self.header.pandoc == Some(false)) {
+            return Err(Box::new(MetaError::Binary {
+                file: self.path.to_string_lossy().to_string(),
+            }));
+        }
+
+        let text = self.construct()?;
+
+        let (input, output) = self.pandoc_io()?;
+        let temp = TempFile(std::env::temp_dir().join(format!(
+            "metaforge-{}-{}.{}",
+            std::process::id(),
+            TEMP_FILES.fetch_add(1, Ordering::Relaxed),
+            self.header.filetype
+        )));
+
+        log!(self.opts, "calling pandoc", 3);
+
+        let mut pandoc = Pandoc::new();
+        pandoc
+            .set_input(InputKind::Pipe(text))
+            .set_output(OutputKind::File(temp.0.clone()))
+            .set_input_format(input, vec![])
+            .set_output_format(output, vec![]);
+        pandoc.execute().map_err(MetaError::from)?;
+
+        Ok(std::fs::read(&temp.0).map_err(|e| MetaError::from(eyre::Error::from(e)))?)
+    }
+}
+
+// removes the temporary file however the build turns out
+struct TempFile(PathBuf);
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        std::fs::remove_file(&self.0).ok();
+    }
+}
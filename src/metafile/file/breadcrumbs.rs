@@ -0,0 +1,92 @@
+use super::*;
+use std::path::Path;
+
+impl<'a> MetaFile<'a> {
+    // BREADCRUMBS.title and BREADCRUMBS.url expand to the trail of
+    // directories from the source root down to the page being built,
+    // or None for any other array
+    pub fn get_breadcrumbs(&self, key: &str) -> Result<Option<Vec<String>>> {
+        let field = match key.strip_prefix("BREADCRUMBS.") {
+            Some(field @ ("title" | "url")) => field,
+            _ => return Ok(None),
+        };
+
+        // patterns get built with the source file at the bottom of the stack
+        let current = self.stack.first().unwrap_or(&self.path);
+        let rel = match current.strip_prefix(&self.opts.source) {
+            Ok(rel) => rel,
+            Err(_) => return Ok(Some(Vec::new())),
+        };
+
+        let mut crumbs = Vec::new();
+
+        let mut dir = self.opts.source.clone();
+        crumbs.push(self.dir_crumb(&dir)?);
+        for component in rel.parent().into_iter().flat_map(|p| p.components()) {
+            dir.push(component);
+            crumbs.push(self.dir_crumb(&dir)?);
+        }
+
+        // an index page is already in the trail as its directory
+        if current.file_name().and_then(|f| f.to_str()) != Some("index.meta") {
+            let title = match self.resolve(&self.variables, "title") {
+                Some(title) => title.to_string(),
+                None => file_stem(current),
+            };
+
+            crumbs.push((title, self.current_url()?));
+        }
+
+        Ok(Some(
+            crumbs
+                .into_iter()
+                .map(|(title, url)| if field == "title" { title } else { url })
+                .collect(),
+        ))
+    }
+
+    // a directory's title comes from its index page, then its default.meta,
+    // then its name. it links to the index page if there is one
+    pub fn dir_crumb(&self, dir: &Path) -> Result<(String, String)> {
+        let index = dir.join("index.meta");
+        let index = if index.exists() {
+            Some(MetaFile::build(index, self.opts)?)
+        } else {
+            None
+        };
+
+        let default = dir.join("default.meta");
+        let default = if default.exists() {
+            Some(MetaFile::build(default, self.opts)?)
+        } else {
+            None
+        };
+
+        let title = [&index, &default]
+            .into_iter()
+            .flatten()
+            .find_map(|file| file.resolve(&file.variables, "title").cloned())
+            .unwrap_or_else(|| {
+                if dir == self.opts.source {
+                    String::from("home")
+                } else {
+                    file_stem(dir)
+                }
+            });
+
+        let url = match index {
+            // the url can depend on settings from default.meta files
+            Some(index) => MetaFile::build_page(index.path, self.opts)?.url()?,
+            None => {
+                let rel = dir.strip_prefix(&self.opts.source)?;
+                if rel.as_os_str().is_empty() {
+                    String::from("/")
+                } else {
+                    format!("/{}/", rel.to_string_lossy())
+                }
+            }
+        };
+
+        Ok((title, url))
+    }
+}
@@ -0,0 +1,117 @@
+use super::*;
+use std::path::Path;
+
+impl<'a> MetaFile<'a> {
+    // the NAV pattern, with the page being built marked as active. the
+    // tree only gets built once a page uses it, then gets reused
+    pub fn get_nav(&self) -> Result<String> {
+        let nav = match self.nav.get() {
+            Some(nav) => nav.clone(),
+            None => {
+                let nav = self.build_nav(&self.opts.source)?;
+                self.nav.get_or_init(|| nav).clone()
+            }
+        };
+
+        self.mark_active(&nav)
+    }
+
+    // renders every listed page under dir as nested lists of links,
+    // with each directory linking to its index page
+    pub fn build_nav(&self, dir: &Path) -> Result<String> {
+        log!(self.opts, format!("building nav for {}", dir.display()), 2);
+        self.nav_list(dir, true)
+    }
+
+    pub fn mark_active(&self, nav: &str) -> Result<String> {
+        let url = self.current_url()?;
+
+        Ok(nav.replace(
+            &format!("<li><a href=\"{url}\">"),
+            &format!("<li class=\"active\"><a href=\"{url}\" aria-current=\"page\">"),
+        ))
+    }
+
+    fn nav_list(&self, dir: &Path, top: bool) -> Result<String> {
+        let mut items = Vec::new();
+        for path in sorted_entries(dir)? {
+            let name = path.file_name().and_then(|f| f.to_str()).unwrap_or("");
+
+            if path.is_dir() {
+                let settings = [path.join("index.meta"), path.join("default.meta")]
+                    .into_iter()
+                    .filter(|file| file.exists())
+                    .map(|file| MetaFile::build(file, self.opts))
+                    .collect::<Result<Vec<MetaFile>, MetaError>>()?;
+
+                if settings.iter().any(|file| {
+                    file.header.nav_exclude || file.header.ignore || file.header.copy_only
+                }) {
+                    continue;
+                }
+
+                let weight = settings
+                    .iter()
+                    .map(|file| file.header.weight)
+                    .find(|weight| *weight != 0)
+                    .unwrap_or_default();
+
+                let (title, url) = self.dir_crumb(&path)?;
+                let title = settings
+                    .iter()
+                    .find_map(|file| file.resolve(&file.variables, "nav_title").cloned())
+                    .unwrap_or(title);
+
+                let children = self.nav_list(&path, false)?;
+                let index = path.join("index.meta");
+                let listed = index.exists() && MetaFile::build_page(index, self.opts)?.is_listed();
+
+                // directories without any listed pages would only be dead links
+                if children.is_empty() && !listed {
+                    continue;
+                }
+
+                let title = escape_xml(&title);
+                items.push((
+                    weight,
+                    if children.is_empty() {
+                        format!("<li><a href=\"{url}\">{title}</a></li>\n")
+                    } else {
+                        format!("<li><a href=\"{url}\">{title}</a>\n{children}</li>\n")
+                    },
+                ));
+            } else if name == "default.meta"
+                || (name == "index.meta" && !top)
+                || path.extension().and_then(|f| f.to_str()) != Some("meta")
+            {
+                continue;
+            } else {
+                let page = MetaFile::build_page(path.clone(), self.opts)?;
+                if !page.is_listed() || page.header.nav_exclude {
+                    continue;
+                }
+
+                let title = ["nav_title", "title"]
+                    .into_iter()
+                    .find_map(|key| page.resolve(&page.variables, key).cloned())
+                    .unwrap_or_else(|| file_stem(&path));
+                let title = escape_xml(&title);
+
+                items.push((
+                    page.header.weight,
+                    format!("<li><a href=\"{}\">{title}</a></li>\n", page.url()?),
+                ));
+            }
+        }
+
+        if items.is_empty() {
+            return Ok(String::new());
+        }
+
+        // lighter items float to the top, with ties staying in filename order
+        items.sort_by_key(|(weight, _)| *weight);
+
+        let items: String = items.into_iter().map(|(_, item)| item).collect();
+        Ok(format!("<ul>\n{items}</ul>\n"))
+    }
+}
@@ -0,0 +1,148 @@
+use super::*;
+use std::path::Path;
+
+impl<'a> MetaFile<'a> {
+    // PAGES.path.to.dir.field expands to the field from every
+    // page in source/path/to/dir, or None for any other array
+    pub fn get_collection(&self, key: &str) -> Result<Option<Vec<String>>> {
+        let key = match key.strip_prefix("PAGES.") {
+            Some(key) => key,
+            None => return Ok(None),
+        };
+
+        let (dir, field) = match key.rsplit_once('.') {
+            Some((dir, field)) => (dir, field),
+            None => ("", key),
+        };
+
+        log!(self.opts, format!("collecting {field} from PAGES.{dir}"), 2);
+
+        // collections are configured with variables, so
+        // ${ PAGES.blog.sort = 'date' } sorts PAGES.blog by date
+        let setting = |name: &str| {
+            let key = if dir.is_empty() {
+                format!("PAGES.{name}")
+            } else {
+                format!("PAGES.{dir}.{name}")
+            };
+
+            self.resolve(&self.variables, &key)
+                .cloned()
+                .unwrap_or_default()
+        };
+
+        let recursive = setting("recursive") == "true";
+        let reverse = setting("reverse") == "true";
+        let sort = setting("sort");
+
+        let path = self.opts.source.join(dir.replace('.', "/"));
+        let mut pages = self.collect_pages(&path, recursive)?;
+
+        // sorts are stable, so pages keep filename order for equal keys
+        if sort.is_empty() || sort == "weight" {
+            pages.sort_by_key(|page| page.header.weight);
+        } else {
+            let mut keyed = pages
+                .into_iter()
+                .map(|page| {
+                    let field = page.get_field(&sort)?;
+                    // dates sort by when they are, not how they're written
+                    let date = if sort == "date" {
+                        parse_date(&field)
+                    } else {
+                        None
+                    };
+                    Ok(((date, field), page))
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            keyed.sort_by(|a, b| a.0.cmp(&b.0));
+            pages = keyed.into_iter().map(|(_, page)| page).collect();
+        }
+
+        if reverse {
+            pages.reverse();
+        }
+
+        // paginated pages only get their slice of the collection
+        if let Some(pagination) = &self.pagination {
+            if pagination.collection == dir {
+                pages = pages
+                    .into_iter()
+                    .skip((pagination.current - 1) * pagination.per_page)
+                    .take(pagination.per_page)
+                    .collect();
+            }
+        }
+
+        let values = pages
+            .iter()
+            .map(|page| page.get_field(field))
+            .collect::<Result<Vec<String>>>()?;
+
+        Ok(Some(values))
+    }
+
+    // parses every listed page in a directory, sorted by path
+    pub fn collect_pages(&self, dir: &Path, recursive: bool) -> Result<Vec<MetaFile<'a>>> {
+        let mut pages = Vec::new();
+
+        if !dir.is_dir() {
+            return Err(MetaError::FileNotFound {
+                path: dir.to_string_lossy().to_string(),
+            }
+            .into());
+        }
+
+        let paths = sorted_entries(dir)?;
+
+        // the page that's being built doesn't list itself
+        let current = self.stack.first().unwrap_or(&self.path);
+
+        for path in paths {
+            if path.is_dir() {
+                if recursive {
+                    pages.append(&mut self.collect_pages(&path, recursive)?);
+                }
+            } else if path.file_name().and_then(|f| f.to_str()) == Some("default.meta")
+                || path.extension().and_then(|f| f.to_str()) != Some("meta")
+                || &path == current
+            {
+                continue;
+            } else {
+                let page = MetaFile::build_page(path, self.opts)?;
+                if page.is_listed() {
+                    pages.push(page);
+                }
+            }
+        }
+
+        Ok(pages)
+    }
+
+    // pages that don't produce an output file don't show up in collections
+    pub fn is_listed(&self) -> bool {
+        !(self.header.ignore || self.header.blank || self.header.copy_only || self.is_unpublished())
+    }
+
+    // drafts and pages scheduled for later get skipped,
+    // unless --drafts or --future say otherwise
+    pub fn is_unpublished(&self) -> bool {
+        let draft = self.header.draft && !self.opts.drafts;
+        let future = !self.opts.future
+            && self
+                .header
+                .publish_date
+                .is_some_and(|date| date > chrono::Utc::now());
+
+        draft || future
+    }
+
+    // the url of a page, or any variable defined for it
+    pub fn get_field(&self, field: &str) -> Result<String> {
+        match field {
+            "url" => self.url(),
+            _ => self.get_variable(field),
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use super::*;
+
+impl<'a> MetaFile<'a> {
+    // splits a file with the paginate header into one file per page of
+    // its collection, or just returns the file if it isn't paginated
+    pub fn paginate(&self) -> Result<Vec<MetaFile<'a>>> {
+        if self.header.paginate.is_empty() {
+            return Ok(vec![self.clone()]);
+        }
+
+        if self.header.per_page == 0 {
+            return Err(MetaError::Header {
+                opt: "per_page = 0".to_string(),
+            }
+            .into());
+        }
+
+        let count = self
+            .get_collection(&format!("PAGES.{}.url", self.header.paginate))?
+            .map_or(0, |pages| pages.len());
+        let total = count.div_ceil(self.header.per_page).max(1);
+
+        log!(
+            self.opts,
+            format!("paginating {} into {total} pages", self.path.display()),
+            2
+        );
+
+        let mut pages = Vec::new();
+        for current in 1..=total {
+            let mut page = self.clone();
+            page.pagination = Some(Pagination {
+                collection: self.header.paginate.clone(),
+                per_page: self.header.per_page,
+                current,
+                total,
+            });
+
+            let prev = if current > 1 {
+                self.page_url(current - 1, total)?
+            } else {
+                String::new()
+            };
+
+            let next = if current < total {
+                self.page_url(current + 1, total)?
+            } else {
+                String::new()
+            };
+
+            for (key, val) in [
+                ("prev", prev),
+                ("next", next),
+                ("current", current.to_string()),
+                ("total", total.to_string()),
+            ] {
+                page.variables
+                    .insert(Scope::create_global(format!("PAGINATION.{key}")), val);
+            }
+
+            pages.push(page);
+        }
+
+        Ok(pages)
+    }
+
+    fn page_url(&self, current: usize, total: usize) -> Result<String> {
+        // permalinks can use the file's variables, so the whole file is needed
+        let mut page = self.clone();
+        page.pagination = Some(Pagination {
+            collection: self.header.paginate.clone(),
+            per_page: self.header.per_page,
+            current,
+            total,
+        });
+
+        page.url()
+    }
+}
@@ -12,16 +12,97 @@ impl<'a> MetaFile<'a> {
             }
         }
 
+        // TOC is built from the converted source in construct(), so
+        // inside the source itself it gets marked to be swapped in later
+        if key == "TOC" {
+            return match self.patterns.get(&Scope::create_global("TOC")) {
+                Some(toc) => Ok(toc.to_string()),
+                None => Ok(super::toc::TOC_MARK.to_string()),
+            };
+        }
+
+        // NAV is generated unless it's overridden like any other pattern
+        if key == "NAV"
+            && matches!(
+                self.resolve(&self.patterns, key).map(|name| name.as_str()),
+                None | Some("DEFAULT") | Some("default")
+            )
+        {
+            return self.get_nav();
+        }
+
+        // RAW:path and BASE64:path insert files without parsing them
+        if let Some((kind, path)) = key.split_once(':') {
+            return self.get_raw(kind, path);
+        }
+
+        // if we're building the base pattern we need to wait on
+        // parsing/expansion so we can build and convert source to html
+        // for the SOURCE pattern. we just want to return the string right now
+        if key == "base" {
+            let path = match self.pattern_path(key)? {
+                Some(path) => path,
+                None => return Ok(String::new()),
+            };
+
+            return match std::fs::read_to_string(&path) {
+                Ok(str) => Ok(str),
+                Err(_) => Err(MetaError::FileNotFound {
+                    path: path.to_string_lossy().to_string(),
+                }
+                .into()),
+            };
+        }
+
+        let pattern = match key.split_once('#') {
+            Some((pattern, _)) => self.build_pattern(pattern)?,
+            None => self.build_pattern(key)?,
+        };
+
+        match pattern {
+            Some(pattern) => pattern.expand_pattern(key),
+            None => Ok(String::new()),
+        }
+    }
+
+    // works like get_pattern(), except patterns with the post_pandoc header
+    // get swapped out for a placeholder and pushed onto the deferred list,
+    // so they can be inserted after the calling file goes through pandoc
+    pub fn get_post_pattern(&self, key: &str, deferred: &mut Vec<String>) -> Result<String> {
+        if key == "SOURCE"
+            || key == "TOC"
+            || key == "NAV"
+            || key == "base"
+            || key.contains(':')
+            || key.contains('#')
+        {
+            return self.get_pattern(key);
+        }
+
+        let pattern = match self.build_pattern(key)? {
+            Some(pattern) => pattern,
+            None => return Ok(String::new()),
+        };
+
+        let post_pandoc = pattern.header.post_pandoc;
+        let expanded = pattern.expand_pattern(key)?;
+
+        if post_pandoc {
+            deferred.push(expanded);
+            Ok(placeholder(deferred.len() - 1))
+        } else {
+            Ok(expanded)
+        }
+    }
+
+    // finds the file for a pattern, returning None for BLANK patterns
+    pub fn pattern_path(&self, key: &str) -> Result<Option<PathBuf>> {
         let is_source = key.split('.').next().unwrap_or("") == "SOURCE";
 
-        let mut filename = if let Some(name) = self.patterns.get(&Scope::create_local(key)) {
-            Ok(name.to_string())
-        } else if let Some(name) = self.patterns.get(&Scope::create_global(key)) {
+        let filename = if let Some(name) = self.resolve(&self.patterns, key) {
             Ok(name.to_string())
         } else if self
-            .opts
-            .pattern
-            .join(key.replace('.', "/") + ".meta")
+            .filetype_variant(self.opts.pattern.join(key.replace('.', "/") + ".meta"))
             .exists()
             || is_source
         {
@@ -38,29 +119,12 @@ impl<'a> MetaFile<'a> {
 
         // BLANK returns nothing, so no more processing needs to be done
         if filename == "BLANK" {
-            return Ok(String::default());
+            return Ok(None);
         };
 
         // DEFAULT override for patterns overriding globals
-        if filename == "DEFAULT" {
-            filename = "default".to_string();
-        }
-
-        // if we're building the base pattern we need to wait on
-        // parsing/expansion so we can build and convert source to html
-        // for the SOURCE pattern. we just want to return the string right now
-        if key == "base" {
-            let pattern_path = key.to_string() + "/" + &filename;
-            let mut path = self.opts.pattern.join(pattern_path);
-            path.set_extension("meta");
-
-            return match std::fs::read_to_string(&path) {
-                Ok(str) => Ok(str),
-                Err(_) => Err(MetaError::FileNotFound {
-                    path: path.to_string_lossy().to_string(),
-                }
-                .into()),
-            };
+        if filename == "DEFAULT" || filename == "default" {
+            return self.default_path(key).map(Some);
         }
 
         let pattern_path = key.replace('.', "/") + "/" + &filename;
@@ -73,15 +137,125 @@ impl<'a> MetaFile<'a> {
         };
 
         path.set_extension("meta");
+
+        Ok(Some(self.filetype_variant(path)))
+    }
+
+    // prefers a pattern specific to the output filetype if one exists,
+    // so base/default.meta becomes base/default.xml.meta for xml output
+    fn filetype_variant(&self, path: PathBuf) -> PathBuf {
+        if self.header.filetype.is_empty() {
+            return path;
+        }
+
+        let variant = path.with_extension(format!("{}.meta", self.header.filetype));
+        if variant.exists() {
+            variant
+        } else {
+            path
+        }
+    }
+
+    // default.meta files cascade, so foo.bar.baz checks foo/bar/baz/default.meta,
+    // then foo/bar/default.meta, then foo/default.meta
+    fn default_path(&self, key: &str) -> Result<PathBuf> {
+        let mut dir = self.opts.pattern.join(key.replace('.', "/"));
+
+        while dir.starts_with(&self.opts.pattern) && dir != self.opts.pattern {
+            let path = self.filetype_variant(dir.join("default.meta"));
+            if path.exists() {
+                return Ok(path);
+            }
+
+            if !dir.pop() {
+                break;
+            }
+        }
+
+        Err(MetaError::UndefinedDefault {
+            pattern: key.to_string(),
+            path: self.path.to_string_lossy().to_string(),
+        }
+        .into())
+    }
+
+    // parses the file for a pattern, returning None for BLANK patterns
+    fn build_pattern(&self, key: &str) -> Result<Option<MetaFile<'a>>> {
+        let path = match self.pattern_path(key)? {
+            Some(path) => path,
+            None => return Ok(None),
+        };
+
+        // keep track of every file being expanded to get here,
+        // so recursive patterns error out instead of overflowing the stack
+        let mut stack = self.stack.clone();
+        stack.push(self.path.clone());
+
+        if stack.contains(&path) {
+            stack.push(path);
+            return Err(MetaError::Cycle {
+                chain: self.display_stack(&stack),
+            }
+            .into());
+        } else if stack.len() > self.opts.max_depth {
+            return Err(MetaError::Depth {
+                depth: self.opts.max_depth,
+                path: path.to_string_lossy().to_string(),
+            }
+            .into());
+        }
+
         let mut pattern = MetaFile::build(path, self.opts)?;
+        pattern.stack = stack;
+        pattern.pagination = self.pagination.clone();
 
         // copy over maps for expanding contained variables
         pattern.merge(self);
+        // patterns get built for the output filetype of the calling file
+        pattern.header.filetype = self.header.filetype.clone();
+
+        Ok(Some(pattern))
+    }
+
+    fn display_stack(&self, stack: &[PathBuf]) -> String {
+        stack
+            .iter()
+            .map(|path| {
+                let mut file = MetaFile::new(self.opts);
+                file.path = path.clone();
+                file.name()
+                    .unwrap_or_else(|_| path.to_string_lossy().to_string())
+            })
+            .collect::<Vec<String>>()
+            .join(" → ")
+    }
+
+    fn expand_pattern(mut self, key: &str) -> Result<String> {
+        let is_source = key.split('.').next().unwrap_or("") == "SOURCE";
 
-        if pattern.header.pandoc.unwrap_or(false) || is_source {
-            pattern.pandoc()
+        // key#id only includes the section under the heading with that id
+        let section = key.split_once('#').map(|(_, section)| section);
+
+        if self.header.pandoc.unwrap_or(false) || is_source {
+            self.pandoc_section(section)
+        } else if let Some(section) = section {
+            self.get_section(section)
         } else {
-            pattern.get_source()
+            self.get_source()
         }
     }
 }
+
+// placeholders need to survive pandoc without being escaped or
+// turned into markup, so they're kept to plain alphanumerics
+pub fn placeholder(index: usize) -> String {
+    format!("METAFORGEPOSTPANDOC{index}X")
+}
+
+// swaps a placeholder out after conversion, taking any paragraph
+// pandoc wrapped around a lone placeholder along with it
+pub fn replace_placeholder(source: &str, mark: &str, value: &str) -> String {
+    source
+        .replace(&format!("<p>{mark}</p>"), value)
+        .replace(mark, value)
+}
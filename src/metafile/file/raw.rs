@@ -0,0 +1,59 @@
+use super::*;
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+impl<'a> MetaFile<'a> {
+    pub fn get_raw(&self, kind: &str, file: &str) -> Result<String> {
+        log!(self.opts, format!("inserting {kind}:{file}"), 2);
+
+        // paths are relative to the source directory, falling back to the root
+        let path = if self.opts.source.join(file).exists() {
+            self.opts.source.join(file)
+        } else {
+            self.opts.root.join(file)
+        };
+
+        let bytes = match std::fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(_) => {
+                return Err(MetaError::FileNotFound {
+                    path: path.to_string_lossy().to_string(),
+                }
+                .into())
+            }
+        };
+
+        match kind {
+            "RAW" => Ok(String::from_utf8_lossy(&bytes).to_string()),
+            "BASE64" => Ok(format!(
+                "data:{};base64,{}",
+                mime_type(&path),
+                STANDARD.encode(bytes)
+            )),
+            _ => Err(MetaError::UndefinedExpand {
+                val: format!("{kind}:{file}"),
+                path: self.path.to_string_lossy().to_string(),
+            }
+            .into()),
+        }
+    }
+}
+
+fn mime_type(path: &std::path::Path) -> &'static str {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("svg") => "image/svg+xml",
+        Some("png") => "image/png",
+        Some("jpg") | Some("jpeg") => "image/jpeg",
+        Some("gif") => "image/gif",
+        Some("webp") => "image/webp",
+        Some("ico") => "image/x-icon",
+        Some("css") => "text/css",
+        Some("js") => "text/javascript",
+        Some("html") => "text/html",
+        Some("txt") => "text/plain",
+        Some("json") => "application/json",
+        Some("woff") => "font/woff",
+        Some("woff2") => "font/woff2",
+        Some("ttf") => "font/ttf",
+        _ => "application/octet-stream",
+    }
+}
@@ -0,0 +1,59 @@
+use super::*;
+
+impl<'a> MetaFile<'a> {
+    // every definition is looked up in the same order:
+    //      [name].key => [class].key => key
+    // with local definitions checked before global ones at each step.
+    // there isn't a separate directory step: definitions from default.meta
+    // are merged in as globals, closest directory first, so the global
+    // step covers them and they already shadow anything further up the tree.
+    // arrays used to check a global key before a local one, but now follow
+    // the same order as variables and patterns
+    pub fn lookup_chain(&self, key: &str) -> Vec<Scope> {
+        let mut chain = Vec::new();
+
+        for prefix in [self.name().ok(), self.class().ok()].into_iter().flatten() {
+            if prefix.is_empty() {
+                continue;
+            }
+
+            let long_key = prefix + "." + key;
+            chain.push(Scope::create_local(&long_key));
+            chain.push(Scope::create_global(&long_key));
+        }
+
+        chain.push(Scope::create_local(key));
+        chain.push(Scope::create_global(key));
+
+        chain
+    }
+
+    pub fn resolve<'m, T>(&self, map: &'m HashMap<Scope, T>, key: &str) -> Option<&'m T> {
+        self.lookup_chain(key)
+            .into_iter()
+            .find_map(|scope| map.get(&scope))
+    }
+
+    // walks the lookup chain for an expansion, pairing each step with
+    // the value defined there. the first defined value is the one used
+    pub fn explain(&self, expansion: &Src) -> Vec<(Scope, Option<String>)> {
+        macro_rules! explain (
+            ($map:ident, $key:expr, $display:expr) => {
+                self.lookup_chain($key)
+                    .into_iter()
+                    .map(|scope| {
+                        let value = self.$map.get(&scope).map($display);
+                        (scope, value)
+                    })
+                    .collect()
+            };
+        );
+
+        match expansion {
+            Src::Var(key) => explain!(variables, key, |val| val.to_string()),
+            Src::Arr(key) => explain!(arrays, key, |val| format!("{val:?}")),
+            Src::Pat(key) => explain!(patterns, key, |val| val.to_string()),
+            Src::Str(_) => Vec::new(),
+        }
+    }
+}
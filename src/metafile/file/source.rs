@@ -2,10 +2,16 @@ use super::*;
 
 impl<'a> MetaFile<'a> {
     pub fn pandoc(&mut self) -> Result<String> {
-        let string = self.get_source()?;
+        self.pandoc_section(None)
+    }
 
-        if self.opts.no_pandoc || string.is_empty() {
-            return Ok(string);
+    // same as pandoc(), but only converts the section of the source
+    // under the heading with the given id, if there is one
+    pub fn pandoc_section(&mut self, section: Option<&str>) -> Result<String> {
+        // binary output gets converted all at once in construct_bytes(),
+        // so anything going into it stays in the source format
+        if self.opts.no_pandoc || self.is_binary() {
+            return self.expand_source(section, None);
         }
 
         let input: InputFormat;
@@ -15,6 +21,13 @@ impl<'a> MetaFile<'a> {
             output = io.1;
         } else {
             // don't run pandoc if a filetype that isn't supported gets requested
+            return self.expand_source(section, None);
+        }
+
+        let mut deferred = Vec::new();
+        let string = self.expand_source(section, Some(&mut deferred))?;
+
+        if string.is_empty() {
             return Ok(string);
         }
 
@@ -27,15 +40,22 @@ impl<'a> MetaFile<'a> {
             .set_input_format(input, vec![])
             .set_output_format(output, vec![]);
 
-        if let pandoc::PandocOutput::ToBuffer(s) = pandoc.execute()? {
+        if let pandoc::PandocOutput::ToBuffer(mut s) = pandoc.execute()? {
             self.header.pandoc = Some(false);
+
+            // swap placeholders back out for post_pandoc patterns
+            for (i, pattern) in deferred.iter().enumerate() {
+                let mark = super::patterns::placeholder(i);
+                s = super::patterns::replace_placeholder(&s, &mark, pattern);
+            }
+
             Ok(s)
         } else {
             Err(MetaError::Pandoc { file: self.name()? }.into())
         }
     }
 
-    fn pandoc_io(&self) -> Result<(pandoc::InputFormat, pandoc::OutputFormat), Box<MetaError>> {
+    pub fn pandoc_io(&self) -> Result<(pandoc::InputFormat, pandoc::OutputFormat), Box<MetaError>> {
         let mut source_type = "";
         if !self.header.source.is_empty() {
             source_type = &self.header.source;
@@ -69,6 +89,9 @@ impl<'a> MetaFile<'a> {
             "latex" => Ok(OutputFormat::Latex),
             "asciidoc" => Ok(OutputFormat::Asciidoc),
             "pdf" => Ok(OutputFormat::Pdf),
+            "docx" => Ok(OutputFormat::Docx),
+            "epub" => Ok(OutputFormat::Epub),
+            "odt" => Ok(OutputFormat::Odt),
             _ => Err(Box::new(MetaError::Filetype)),
         }?;
 
@@ -76,6 +99,20 @@ impl<'a> MetaFile<'a> {
     }
 
     pub fn get_source(&self) -> Result<String> {
+        self.expand_source(None, None)
+    }
+
+    pub fn get_section(&self, section: &str) -> Result<String> {
+        self.expand_source(Some(section), None)
+    }
+
+    // expands the source, optionally cutting it down to a single section
+    // and deferring post_pandoc patterns
+    fn expand_source(
+        &self,
+        section: Option<&str>,
+        mut deferred: Option<&mut Vec<String>>,
+    ) -> Result<String> {
         if self.header.blank {
             return Ok(String::new());
         }
@@ -89,7 +126,10 @@ impl<'a> MetaFile<'a> {
                 Src::Str(str) => str.to_string(),
                 // expand all variables and recursively expand patterns
                 Src::Var(key) => self.get_variable(key)?,
-                Src::Pat(key) => self.get_pattern(key)?,
+                Src::Pat(key) => match deferred.as_deref_mut() {
+                    Some(deferred) => self.get_post_pattern(key, deferred)?,
+                    None => self.get_pattern(key)?,
+                },
                 Src::Arr(key) => {
                     arrays = true;
                     // comments have already been removed at this point,
@@ -102,9 +142,61 @@ impl<'a> MetaFile<'a> {
         }
 
         if arrays {
-            self.expand_arrays(output)
-        } else {
-            Ok(output)
+            output = self.expand_arrays(output)?;
+        }
+
+        match section {
+            Some(id) => find_section(&output, id).ok_or_else(|| {
+                MetaError::UndefinedExpand {
+                    val: format!("#{id}"),
+                    path: self.path.to_string_lossy().to_string(),
+                }
+                .into()
+            }),
+            None => Ok(output),
         }
     }
 }
+
+// finds a markdown heading by its id, either set explicitly with {#id}
+// or generated from the heading text the same way pandoc does, and returns
+// everything up to the next heading of the same or higher level
+fn find_section(source: &str, id: &str) -> Option<String> {
+    let mut section: Option<(usize, String)> = None;
+    let mut fenced = false;
+
+    for line in source.split_inclusive('\n') {
+        if line.trim_start().starts_with("```") || line.trim_start().starts_with("~~~") {
+            fenced = !fenced;
+        }
+
+        let level = line.chars().take_while(|c| *c == '#').count();
+        let is_heading = !fenced
+            && (1..=6).contains(&level)
+            && line[level..].starts_with(|c: char| c.is_whitespace());
+
+        if let Some((section_level, text)) = section.as_mut() {
+            if is_heading && level <= *section_level {
+                break;
+            }
+            text.push_str(line);
+        } else if is_heading && heading_id(&line[level..]) == id {
+            section = Some((level, line.to_string()));
+        }
+    }
+
+    section.map(|(_, text)| text)
+}
+
+fn heading_id(heading: &str) -> String {
+    let heading = heading.trim().trim_end_matches('#').trim();
+
+    if let Some(start) = heading.rfind("{#") {
+        if heading.ends_with('}') {
+            let attrs = &heading[start + 2..heading.len() - 1];
+            return attrs.split_whitespace().next().unwrap_or("").to_string();
+        }
+    }
+
+    slugify(heading)
+}
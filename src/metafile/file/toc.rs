@@ -0,0 +1,119 @@
+use super::*;
+
+// stands in for &{TOC} in the source until it's been converted,
+// since the headings aren't known before then
+pub const TOC_MARK: &str = "METAFORGETOCX";
+
+impl<'a> MetaFile<'a> {
+    // the table of contents for converted source, which is only
+    // built for html output
+    pub fn toc(&self, source: &str) -> String {
+        if self.header.filetype == "html" {
+            toc(source, self.header.toc_depth)
+        } else {
+            String::new()
+        }
+    }
+}
+
+// builds a nested list of links to every heading in some html
+// with an id, down to the given heading level
+pub fn toc(html: &str, depth: usize) -> String {
+    let headings = headings(html, depth);
+    let min = match headings.iter().map(|h| h.0).min() {
+        Some(min) => min,
+        None => return String::new(),
+    };
+
+    let mut output = String::new();
+    let mut current = 0;
+
+    for (level, id, text) in headings {
+        let level = level - min + 1;
+
+        if level > current {
+            while current < level {
+                output.push_str(if current == 0 {
+                    "<ul>\n<li>"
+                } else {
+                    "\n<ul>\n<li>"
+                });
+                current += 1;
+            }
+        } else {
+            while current > level {
+                output.push_str("</li>\n</ul>\n");
+                current -= 1;
+            }
+            output.push_str("</li>\n<li>");
+        }
+
+        output.push_str(&format!("<a href=\"#{id}\">{text}</a>"));
+    }
+
+    while current > 0 {
+        output.push_str("</li>\n</ul>\n");
+        current -= 1;
+    }
+
+    output
+}
+
+// finds (level, id, text) for each heading, stripping any tags from the text
+fn headings(html: &str, depth: usize) -> Vec<(usize, String, String)> {
+    let mut headings = Vec::new();
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<h") {
+        rest = &rest[start + 2..];
+
+        let level = match rest.chars().next().and_then(|c| c.to_digit(10)) {
+            Some(level @ 1..=6) => level as usize,
+            _ => continue,
+        };
+
+        let (attrs, content) = match rest[1..].split_once('>') {
+            Some(split) => split,
+            None => break,
+        };
+
+        let close = format!("</h{level}>");
+        let text = match content.find(&close) {
+            Some(end) => &content[..end],
+            None => break,
+        };
+
+        if level <= depth {
+            if let Some(id) = attribute(attrs, "id") {
+                headings.push((level, id, strip_tags(text).trim().to_string()));
+            }
+        }
+
+        rest = content;
+    }
+
+    headings
+}
+
+fn attribute(attrs: &str, name: &str) -> Option<String> {
+    let start = attrs.find(&format!(" {name}=\""))? + name.len() + 3;
+    let end = attrs[start..].find('"')?;
+
+    Some(attrs[start..start + end].to_string())
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => (),
+        }
+    }
+
+    text
+}
@@ -7,23 +7,37 @@ impl<'a> MetaFile<'a> {
             format!("substituting {key} in {}", self.path.display()),
             2
         );
-        let long_key = self.name()? + "." + key;
-        if let Some(val) = self.variables.get(&Scope::create_local(&long_key)) {
-            Ok(val.clone())
-        } else if let Some(val) = self.variables.get(&Scope::create_global(&long_key)) {
-            Ok(val.clone())
-        } else if let Some(val) = self.variables.get(&Scope::create_local(key)) {
-            Ok(val.clone())
-        } else if let Some(val) = self.variables.get(&Scope::create_global(key)) {
+
+        // SOURCE.path.to.file:key gets a variable from another source file
+        if let Some((page, key)) = key.split_once(':') {
+            return self.get_page_variable(page, key);
+        }
+
+        if let Some(val) = self.resolve(&self.variables, key) {
             Ok(val.clone())
         } else if self.opts.undefined || self.header.panic_undefined {
-            return Err(MetaError::UndefinedExpand {
+            Err(MetaError::UndefinedExpand {
                 val: key.to_string(),
-                path: self.name()?,
+                path: self.path.to_string_lossy().to_string(),
             }
-            .into());
+            .into())
         } else {
             Ok(String::new())
         }
     }
+
+    fn get_page_variable(&self, page: &str, key: &str) -> Result<String> {
+        let path = match page.strip_prefix("SOURCE.") {
+            Some(path) => self.opts.source.join(path.replace('.', "/") + ".meta"),
+            None => {
+                return Err(MetaError::UndefinedExpand {
+                    val: format!("{page}:{key}"),
+                    path: self.path.to_string_lossy().to_string(),
+                }
+                .into())
+            }
+        };
+
+        MetaFile::build_page(path, self.opts)?.get_variable(key)
+    }
 }
@@ -1,6 +1,7 @@
+use chrono::{DateTime, FixedOffset};
 use std::collections::HashMap;
 
-use crate::MetaError;
+use crate::{parse_date, MetaError};
 
 #[derive(Debug, Clone, Default)]
 pub struct Header {
@@ -14,6 +15,20 @@ pub struct Header {
     pub ignore: bool,
     pub copy_only: bool,
     pub minify: bool,
+    pub post_pandoc: bool,
+    pub paginate: String,
+    pub per_page: usize,
+    pub feed: bool,
+    pub sitemap: bool,
+    pub toc_depth: usize,
+    pub weight: i64,
+    pub nav_exclude: bool,
+    pub draft: bool,
+    pub publish_date: Option<DateTime<FixedOffset>>,
+    pub permalink: String,
+    pub pretty_urls: Option<bool>,
+    pub aliases: Vec<String>,
+    pub formats: Vec<String>,
 }
 
 impl Header {
@@ -29,8 +44,38 @@ impl Header {
             ignore: false,
             copy_only: false,
             minify: true,
+            post_pandoc: false,
+            paginate: String::new(),
+            per_page: 10,
+            feed: false,
+            sitemap: true,
+            toc_depth: 3,
+            weight: 0,
+            nav_exclude: false,
+            draft: false,
+            publish_date: None,
+            permalink: String::new(),
+            pretty_urls: None,
+            aliases: Vec::new(),
+            formats: Vec::new(),
         }
     }
+
+    // array values in the header, which get parsed separately
+    pub fn set_arrays(&mut self, arrays: HashMap<String, Vec<String>>) -> Result<(), MetaError> {
+        for (key, val) in arrays {
+            match &key[..] {
+                "aliases" => self.aliases = val,
+                // the first format is used for links to the file
+                "filetype" => {
+                    self.filetype = val.first().cloned().unwrap_or_default();
+                    self.formats = val;
+                }
+                x => return Err(MetaError::Header { opt: x.to_string() }),
+            }
+        }
+        Ok(())
+    }
 }
 
 impl TryFrom<HashMap<String, String>> for Header {
@@ -49,6 +94,34 @@ impl TryFrom<HashMap<String, String>> for Header {
                 "ignore" => header.ignore = val == "true",
                 "copy_only" => header.copy_only = val == "true",
                 "minify" => header.minify = val == "true",
+                "post_pandoc" => header.post_pandoc = val == "true",
+                "feed" => header.feed = val == "true",
+                "sitemap" => header.sitemap = val == "true",
+                "nav_exclude" => header.nav_exclude = val == "true",
+                "draft" => header.draft = val == "true",
+                "publish_date" => {
+                    header.publish_date = Some(parse_date(val).ok_or(MetaError::Header {
+                        opt: format!("{key} = {val}"),
+                    })?)
+                }
+                "permalink" => header.permalink = val.to_string(),
+                "pretty_urls" => header.pretty_urls = Some(val == "true"),
+                "paginate" => header.paginate = val.to_string(),
+                "per_page" => {
+                    header.per_page = val.parse().map_err(|_| MetaError::Header {
+                        opt: format!("{key} = {val}"),
+                    })?
+                }
+                "weight" | "order" => {
+                    header.weight = val.parse().map_err(|_| MetaError::Header {
+                        opt: format!("{key} = {val}"),
+                    })?
+                }
+                "toc_depth" => {
+                    header.toc_depth = val.parse().map_err(|_| MetaError::Header {
+                        opt: format!("{key} = {val}"),
+                    })?
+                }
                 x => return Err(MetaError::Header { opt: x.to_string() }),
             }
         }
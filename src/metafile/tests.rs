@@ -28,6 +28,50 @@ fn test_name() -> Result<()> {
     assert_eq!(pat2.name()?, "test.class.file");
     assert_eq!(pat1.class()?, "base");
     assert_eq!(pat2.class()?, "test.class");
+    assert_eq!(src.class()?, "test");
+
+    Ok(())
+}
+
+#[test]
+fn test_lookup_chain() -> Result<()> {
+    let mut opts = Options::new();
+
+    opts.source = "/tmp/source".into();
+    opts.build = "/tmp/build".into();
+    opts.pattern = "/tmp/pattern".into();
+
+    let mut pat = MetaFile::new(&opts);
+    pat.path = PathBuf::from("/tmp/pattern/test/class/file.meta");
+
+    assert_eq!(
+        pat.lookup_chain("var"),
+        vec![
+            Scope::create_local("test.class.file.var"),
+            Scope::create_global("test.class.file.var"),
+            Scope::create_local("test.class.var"),
+            Scope::create_global("test.class.var"),
+            Scope::create_local("var"),
+            Scope::create_global("var"),
+        ]
+    );
+
+    pat.variables
+        .insert(Scope::create_global("var"), "GLOBAL".into());
+    assert_eq!(pat.get_variable("var")?, "GLOBAL");
+
+    pat.variables
+        .insert(Scope::create_global("test.class.var"), "CLASS".into());
+    assert_eq!(pat.get_variable("var")?, "CLASS");
+
+    pat.variables
+        .insert(Scope::create_local("test.class.file.var"), "NAME".into());
+    assert_eq!(pat.get_variable("var")?, "NAME");
+
+    let explained = pat.explain(&Src::to_var("var"));
+    assert_eq!(explained.len(), 6);
+    assert_eq!(explained[0].1, Some("NAME".to_string()));
+    assert_eq!(explained[1].1, None);
 
     Ok(())
 }
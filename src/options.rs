@@ -23,18 +23,42 @@ pub struct Opts {
     /// builds a single file and outputs on stdout
     #[arg(short, long, value_name = "FILENAME")]
     pub file: Option<String>,
+    /// url the site gets served from, used for feeds
+    #[arg(short = 'u', long, value_name = "URL")]
+    pub base_url: Option<String>,
+    /// show how KEY gets resolved in the file passed with --file
+    #[arg(short, long, value_name = "KEY", requires = "file")]
+    pub explain: Option<String>,
     /// output filetype [html]
     #[arg(short, long, value_name = "OUTPUT_FILETYPE")]
     pub output: Option<String>,
     /// input filetype [markdown]
     #[arg(short, long, value_name = "INPUT_FILETYPE")]
     pub input: Option<String>,
+    /// generate listing pages for each term in the array NAME. repeatable
+    #[arg(short, long, value_name = "NAME")]
+    pub taxonomy: Vec<String>,
     /// enable extra output. repeated flags give more info
     #[arg(short, long, action = clap::ArgAction::Count)]
     pub verbose: u8,
     /// minimal output [false]
     #[arg(short, long, default_value_t = false)]
     pub quiet: bool,
+    /// write sitemap.xml for the site, using --base-url [false]
+    #[arg(long, default_value_t = false, requires = "base_url")]
+    pub sitemap: bool,
+    /// build pages with the draft header [false]
+    #[arg(long, default_value_t = false)]
+    pub drafts: bool,
+    /// build pages with a publish_date that hasn't happened yet [false]
+    #[arg(long, default_value_t = false)]
+    pub future: bool,
+    /// build foo.meta to foo/index.html [false]
+    #[arg(long, default_value_t = false)]
+    pub pretty_urls: bool,
+    /// write a _redirects file for aliases [false]
+    #[arg(long, default_value_t = false)]
+    pub redirects: bool,
     /// enable parallel processing [false]
     #[arg(short = 'l', long, default_value_t = false)]
     pub parallel: bool,
@@ -50,6 +74,9 @@ pub struct Opts {
     /// stop on undefined variables and arrays [false]
     #[arg(long, default_value_t = false)]
     pub undefined: bool,
+    /// maximum depth of nested pattern expansions [64]
+    #[arg(long, value_name = "DEPTH")]
+    pub max_depth: Option<usize>,
     /// don't call pandoc on source files
     #[arg(long, default_value_t = false)]
     pub no_pandoc: bool,
@@ -65,6 +92,8 @@ pub struct Options {
     pub build: PathBuf,
     pub pattern: PathBuf,
     pub file: Option<PathBuf>,
+    pub base_url: String,
+    pub explain: Option<String>,
     pub input: String,
     pub output: String,
     pub verbose: u8,
@@ -76,6 +105,13 @@ pub struct Options {
     pub no_pandoc: bool,
     pub new: bool,
     pub minify: bool,
+    pub max_depth: usize,
+    pub taxonomies: Vec<String>,
+    pub sitemap: bool,
+    pub drafts: bool,
+    pub future: bool,
+    pub pretty_urls: bool,
+    pub redirects: bool,
 }
 
 impl Options {
@@ -86,6 +122,8 @@ impl Options {
             build: PathBuf::new(),
             pattern: PathBuf::new(),
             file: None,
+            base_url: String::default(),
+            explain: None,
             input: String::default(),
             output: String::default(),
             verbose: 0,
@@ -97,6 +135,13 @@ impl Options {
             no_pandoc: false,
             new: false,
             minify: true,
+            max_depth: 64,
+            taxonomies: Vec::new(),
+            sitemap: false,
+            drafts: false,
+            future: false,
+            pretty_urls: false,
+            redirects: false,
         }
     }
 }
@@ -115,6 +160,21 @@ impl TryFrom<crate::Opts> for Options {
         opts.new = value.new;
         opts.parallel = value.parallel;
         opts.minify = !value.no_minify;
+        opts.explain = value.explain;
+        opts.taxonomies = value.taxonomy;
+        opts.sitemap = value.sitemap;
+        opts.drafts = value.drafts;
+        opts.future = value.future;
+        opts.pretty_urls = value.pretty_urls;
+        opts.redirects = value.redirects;
+
+        if let Some(url) = value.base_url {
+            opts.base_url = url.trim_end_matches('/').to_string();
+        }
+
+        if let Some(depth) = value.max_depth {
+            opts.max_depth = depth;
+        }
 
         opts.root = if let Some(root) = value.root.as_deref() {
             PathBuf::from(root).canonicalize()
@@ -22,7 +22,7 @@ use pest::{
 #[grammar = "parser/meta.pest"]
 pub struct MetaParser;
 
-pub fn parse_string(file: String, opts: &Options) -> Result<MetaFile> {
+pub fn parse_string(file: String, opts: &Options) -> Result<MetaFile<'_>> {
     let pair = MetaParser::parse(Rule::file, &file)?.next().unwrap();
 
     let mut meta_file = MetaFile::new(opts);
@@ -32,10 +32,12 @@ pub fn parse_string(file: String, opts: &Options) -> Result<MetaFile> {
             match pair.as_rule() {
                 Rule::source => meta_file.source = parse_source(pair.into_inner()),
                 Rule::header => {
-                    meta_file.header = Header::try_from(parse_header_defs(pair.into_inner()))?
+                    let (values, arrays) = parse_header_defs(pair.into_inner());
+                    meta_file.header = Header::try_from(values)?;
+                    meta_file.header.set_arrays(arrays)?;
                 }
                 Rule::var_def => meta_file.variables = parse_defs(pair.into_inner())?,
-                Rule::arr_def => meta_file.arrays = parse_array_defs(pair.into_inner())?,
+                Rule::arr_def => meta_file.arrays = parse_array_defs(pair.into_inner(), opts)?,
                 Rule::pat_def => meta_file.patterns = parse_defs(pair.into_inner())?,
                 // do nothing on end of file
                 Rule::EOI => continue,
@@ -1,20 +1,20 @@
-use crate::{MetaError, Rule, Scope};
+use crate::{MetaError, Options, Rule, Scope};
 use eyre::Result;
 use pest::iterators::{Pair, Pairs};
-use std::collections::HashMap;
+use std::{collections::HashMap, path::PathBuf};
 
-pub fn parse_array_defs(pairs: Pairs<Rule>) -> Result<HashMap<Scope, Vec<String>>> {
+pub fn parse_array_defs(pairs: Pairs<Rule>, opts: &Options) -> Result<HashMap<Scope, Vec<String>>> {
     let mut map = HashMap::new();
     for pair in pairs {
         if Rule::assign == pair.as_rule() {
-            let (key, val) = parse_assign_array(pair)?;
+            let (key, val) = parse_assign_array(pair, opts)?;
             map.insert(key, val);
         }
     }
     Ok(map)
 }
 
-fn parse_assign_array(pair: Pair<Rule>) -> Result<(Scope, Vec<String>)> {
+fn parse_assign_array(pair: Pair<Rule>, opts: &Options) -> Result<(Scope, Vec<String>)> {
     let mut key = "";
     let mut val = Vec::default();
     let mut global = true;
@@ -22,14 +22,16 @@ fn parse_assign_array(pair: Pair<Rule>) -> Result<(Scope, Vec<String>)> {
     for pair in pair.into_inner() {
         match pair.as_rule() {
             Rule::scope => {
-                if pair.as_str() == "*" {
-                    global = false;
-                } else {
-                    global = true;
-                }
+                global = pair.as_str() != "*";
             }
             Rule::key => key = pair.as_str(),
-            Rule::value => val = parse_array(pair.into_inner()),
+            Rule::value => {
+                let inner = pair.clone().into_inner().next();
+                val = match inner {
+                    Some(glob) if glob.as_rule() == Rule::glob => parse_glob(glob, opts)?,
+                    _ => parse_array(pair.into_inner()),
+                }
+            }
             _ => {
                 return Err(MetaError::UnreachableRule {
                     input: pair.to_string(),
@@ -46,7 +48,7 @@ fn parse_assign_array(pair: Pair<Rule>) -> Result<(Scope, Vec<String>)> {
     }
 }
 
-fn parse_array(pairs: Pairs<Rule>) -> Vec<String> {
+pub fn parse_array(pairs: Pairs<Rule>) -> Vec<String> {
     let mut vec: Vec<String> = Vec::default();
 
     for pair in pairs {
@@ -61,3 +63,40 @@ fn parse_array(pairs: Pairs<Rule>) -> Vec<String> {
 
     vec
 }
+
+fn parse_glob(pair: Pair<Rule>, opts: &Options) -> Result<Vec<String>> {
+    // the only child of a glob is the quoted pattern string
+    let tmp = pair.into_inner().as_str();
+    let pattern = &tmp[1..tmp.len() - 1];
+
+    // globs are matched against the source directory first, then
+    // the root directory if nothing in the source directory matches
+    for dir in [&opts.source, &opts.root] {
+        let mut paths = glob::glob(&dir.join(pattern).to_string_lossy())
+            .map_err(|e| MetaError::Glob {
+                pattern: pattern.to_string(),
+                error: e.to_string(),
+            })?
+            .filter_map(|path| path.ok())
+            .collect::<Vec<PathBuf>>();
+
+        if paths.is_empty() {
+            continue;
+        }
+
+        // sort so the array order doesn't depend on the filesystem
+        paths.sort();
+
+        return Ok(paths
+            .iter()
+            .map(|path| {
+                path.strip_prefix(dir)
+                    .unwrap_or(path)
+                    .to_string_lossy()
+                    .to_string()
+            })
+            .collect());
+    }
+
+    Ok(Vec::new())
+}
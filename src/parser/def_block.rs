@@ -14,7 +14,7 @@ pub fn parse_defs(pairs: Pairs<Rule>) -> Result<HashMap<Scope, String>> {
     Ok(map)
 }
 
-fn parse_assign(pair: Pair<Rule>) -> Result<(Scope, &str)> {
+fn parse_assign(pair: Pair<'_, Rule>) -> Result<(Scope, &str)> {
     let mut key = "";
     let mut val = "";
     let mut global = true;
@@ -23,14 +23,17 @@ fn parse_assign(pair: Pair<Rule>) -> Result<(Scope, &str)> {
     for pair in pair.into_inner() {
         match pair.as_rule() {
             Rule::scope => {
-                if pair.as_str() == "*" {
-                    global = false;
-                } else {
-                    global = true;
-                }
+                global = pair.as_str() != "*";
             }
             Rule::key => key = pair.as_str(),
             Rule::value => {
+                // globs only make sense as arrays
+                if pair.clone().into_inner().any(|p| p.as_rule() == Rule::glob) {
+                    return Err(MetaError::GlobVariable {
+                        input: pair.as_str().to_string(),
+                    }
+                    .into());
+                }
                 val = pair.as_str();
                 if val == "BLANK" || val == "DEFAULT" {
                     trim = false;
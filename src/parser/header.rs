@@ -1,19 +1,44 @@
+use super::array::parse_array;
 use crate::Rule;
 use pest::iterators::{Pair, Pairs};
 use std::collections::HashMap;
 
-pub fn parse_header_defs(pairs: Pairs<Rule>) -> HashMap<String, String> {
+// header settings are either single values or arrays,
+// which get returned in separate maps
+pub fn parse_header_defs(
+    pairs: Pairs<Rule>,
+) -> (HashMap<String, String>, HashMap<String, Vec<String>>) {
     let mut map = HashMap::new();
+    let mut arrays = HashMap::new();
     for pair in pairs {
         if Rule::header_assign == pair.as_rule() {
-            let (key, val) = parse_header_assign(pair);
-            map.insert(key.to_string(), val.to_string());
+            if let Some((key, val)) = parse_header_array(pair.clone()) {
+                arrays.insert(key.to_string(), val);
+            } else {
+                let (key, val) = parse_header_assign(pair);
+                map.insert(key.to_string(), val.to_string());
+            }
+        }
+    }
+    (map, arrays)
+}
+
+fn parse_header_array(pair: Pair<'_, Rule>) -> Option<(&str, Vec<String>)> {
+    let mut key = "";
+
+    for pair in pair.into_inner() {
+        if Rule::key == pair.as_rule() {
+            key = pair.as_str();
+        }
+        if Rule::header_value == pair.as_rule() && pair.as_str().starts_with('[') {
+            return Some((key, parse_array(pair.into_inner())));
         }
     }
-    map
+
+    None
 }
 
-fn parse_header_assign(pair: Pair<Rule>) -> (&str, &str) {
+fn parse_header_assign(pair: Pair<'_, Rule>) -> (&str, &str) {
     let mut key = "";
     let mut val = "";
 
@@ -19,7 +19,7 @@ pub fn parse_source(pairs: Pairs<Rule>) -> Vec<Src> {
     vec
 }
 
-fn parse_sub(pair: Pair<Rule>) -> &str {
+fn parse_sub(pair: Pair<'_, Rule>) -> &str {
     match pair.as_rule() {
         Rule::var_sub | Rule::arr_sub | Rule::pat_sub => {
             let str = pair.as_str();
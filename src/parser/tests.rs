@@ -80,3 +80,62 @@ fn map_source_map() {
 fn header_not_first() {
     test_str!(r#"${v='v'} #{ type = 'html'} @{a=['a']}"#);
 }
+
+#[test]
+fn glob_in_array() {
+    test_str!(r#"@{ a = GLOB('*.meta') b = GLOB( "*" ) }"#);
+}
+
+#[test]
+#[should_panic]
+fn glob_in_variable() {
+    test_str!(r#"${ v = GLOB('*.meta') }"#);
+}
+
+#[test]
+fn glob_in_variable_error() {
+    let opts = crate::Options::new();
+    let err = crate::parse_string(r#"&{ p = GLOB('*.meta') }"#.to_string(), &opts).unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<crate::MetaError>(),
+        Some(crate::MetaError::GlobVariable { .. })
+    ));
+}
+
+#[test]
+fn raw_file_path() {
+    test_str!(r#"<style>&{RAW:static/css/main.min.css}</style>"#);
+}
+
+#[test]
+#[should_panic]
+fn raw_file_spaces() {
+    test_str!(r#"&{RAW:static/bad path.css}"#);
+}
+
+#[test]
+fn source_section() {
+    test_str!(r#"&{SOURCE.docs.flags#usage} &{shared#install-notes_2}"#);
+}
+
+#[test]
+fn page_variable() {
+    test_str!(r#"${SOURCE.blog.post1:title}"#);
+}
+
+#[test]
+#[should_panic]
+fn page_array() {
+    test_str!(r#"@{SOURCE.blog.post1:tags}"#);
+}
+
+#[test]
+fn header_array() {
+    test_str!("#{ aliases = ['/old.html', '/older/'] }\nsource");
+}
+
+#[test]
+#[should_panic]
+fn unknown_header_array() {
+    test_str!("#{ unknown = ['a'] }");
+}
@@ -2,6 +2,28 @@ use crate::{MetaError, MetaFile, Options};
 use eyre::Result;
 use std::{fs, path::PathBuf};
 
+// options pointing at the test site, for tests that set up their own files
+fn test_opts() -> Options {
+    let dir = PathBuf::from("files/test_site")
+        .canonicalize()
+        .unwrap_or_default();
+
+    let mut opts = Options::new();
+    opts.root = dir.clone();
+    opts.source = dir.join("source");
+    opts.build = dir.join("build");
+    opts.pattern = dir.join("pattern");
+
+    opts
+}
+
+// a source directory mapped under an empty global, ready to build
+fn mapped_dir(path: PathBuf, opts: &Options) -> Result<crate::DirNode<'_>> {
+    let mut dir_node = crate::DirNode::build(path, opts)?;
+    dir_node.map(&MetaFile::new(opts))?;
+    Ok(dir_node)
+}
+
 macro_rules! unit_test (
     ($name:ident, $file:expr,$test:literal) => {
         #[test]
@@ -153,6 +175,83 @@ unit_test!(
     "<html>\n<p>GOOD GOOD</p>\n\n\n\n</html>\n"
 );
 
+unit_test!(
+    glob_array,
+    "expand/glob",
+    "unit_tests/glob/photos/a.jpg\nunit_tests/glob/photos/b.jpg\nunit_tests/glob/photos/c.jpg\n\n"
+);
+
+unit_test!(
+    raw_file,
+    "expand/raw",
+    "<svg>${not_a_variable}</svg>\ndata:text/plain;base64,aGk=\n\n"
+);
+
+unit_test!(
+    post_pandoc,
+    "expand/post_pandoc",
+    "<div>GOOD</div>\n\n\n"
+);
+
+unit_test!(cascade_default, "expand/cascade", "GOOD\n\n\n");
+
+unit_test!(nav_blank, "expand/nav_blank", "[]\n\n");
+
+unit_test!(filetype_variant, "header/variant", "TXT\n\n\n");
+
+unit_test!(no_filetype_variant, "header/no_variant", "HTML\n\n\n");
+
+unit_test!(
+    page_variable,
+    "expand/page_variable",
+    "First Post BLOG GOOD\n\n"
+);
+
+unit_test!(
+    page_collection,
+    "expand/collection",
+    "<a href=\"/unit_tests/blog/second.html\">Second Post</a>\n<a href=\"/unit_tests/blog/first.html\">First Post</a>\n\n\n"
+);
+
+unit_test!(
+    toc,
+    "expand/toc",
+    "<ul>
+<li><a href=\"#top\">Top</a>
+<ul>
+<li><a href=\"#install\">Install it</a>
+<ul>
+<li><a href=\"#deps\">Deps</a></li>
+</ul>
+</li>
+<li><a href=\"#usage\">Usage</a></li>
+</ul>
+</li>
+</ul>
+
+<h1 id=\"top\">Top</h1>
+<h2 id=\"install\">Install <code>it</code></h2>
+<h3 id=\"deps\">Deps</h3>
+<h4 id=\"deep\">Too Deep</h4>
+<h2 id=\"usage\">Usage</h2>
+<h2>No Id</h2>
+
+"
+);
+
+unit_test!(
+    breadcrumbs,
+    "crumbs/deep/page",
+    "<a href=\"/\">home</a>
+<a href=\"/unit_tests/\">unit_tests</a>
+<a href=\"/unit_tests/crumbs/index.html\">Crumbs</a>
+<a href=\"/unit_tests/crumbs/deep/\">Deep Dir</a>
+<a href=\"/unit_tests/crumbs/deep/page.html\">Page</a>
+
+
+"
+);
+
 panic_test!(ignore, "ignore.meta", "");
 
 #[test]
@@ -210,3 +309,745 @@ fn test_global() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_post_pandoc_placeholder() -> Result<()> {
+    let opts = test_opts();
+
+    let path = opts.source.join("unit_tests/expand/post_pandoc.meta");
+    let file = MetaFile::build(path, &opts)?;
+
+    let mut deferred = Vec::new();
+    let mark = file.get_post_pattern("test.widget", &mut deferred)?;
+
+    assert_eq!(mark, "METAFORGEPOSTPANDOC0X");
+    assert_eq!(deferred, vec!["<div>GOOD</div>\n".to_string()]);
+    assert_eq!(file.get_post_pattern("test.good", &mut deferred)?, "GOOD\n");
+
+    Ok(())
+}
+
+#[test]
+fn test_missing_default() -> Result<()> {
+    let opts = test_opts();
+
+    let path = opts.source.join("unit_tests/expand/cascade.meta");
+    let file = MetaFile::build(path, &opts)?;
+
+    let err = file.get_pattern("missing.pattern").unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<MetaError>(),
+        Some(MetaError::UndefinedDefault { .. })
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_explain() -> Result<()> {
+    let mut opts = test_opts();
+    opts.file = Some(opts.source.join("unit_tests/blog/first.meta"));
+    opts.explain = Some("section".to_string());
+
+    // section is only defined in the directory's default.meta
+    let explained = crate::explain(&opts)?;
+    assert!(explained.contains("\n    section = BLOG <= used\n"));
+
+    Ok(())
+}
+
+#[test]
+fn test_expansion_cycle() -> Result<()> {
+    let opts = test_opts();
+
+    let path = opts.source.join("unit_tests/expand/cascade.meta");
+    let file = MetaFile::build(path, &opts)?;
+
+    let err = file.get_pattern("cycle.a").unwrap_err();
+    match err.downcast_ref::<MetaError>() {
+        Some(MetaError::Cycle { chain }) => assert_eq!(
+            chain,
+            "unit_tests.expand.cascade → cycle.a → cycle.b → cycle.a"
+        ),
+        _ => panic!("expected a cycle error, got {err}"),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_expansion_depth() -> Result<()> {
+    let mut opts = test_opts();
+    opts.max_depth = 1;
+
+    let path = opts.source.join("unit_tests/expand/cascade.meta");
+    let file = MetaFile::build(path, &opts)?;
+
+    assert_eq!(file.get_pattern("test.good")?, "GOOD\n");
+
+    let err = file.get_pattern("test.expand_pat").unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<MetaError>(),
+        Some(MetaError::Depth { depth: 1, .. })
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_source_section() -> Result<()> {
+    let mut opts = test_opts();
+    opts.no_pandoc = true;
+
+    let path = opts.source.join("unit_tests/fragment/include.meta");
+    let mut file = MetaFile::build(path, &opts)?;
+
+    assert_eq!(
+        file.construct()?,
+        "## Install Instructions\n\nrun the installer\n\n### Details\n\n```\n# not a heading\n```\n\n\n---\n## Usage {#usage-notes}\n\nrun the program\n\n\n"
+    );
+
+    let err = file
+        .get_pattern("SOURCE.unit_tests.fragment.shared#missing")
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<MetaError>(),
+        Some(MetaError::UndefinedExpand { .. })
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_taxonomy() -> Result<()> {
+    let mut opts = test_opts();
+    opts.minify = false;
+    opts.taxonomies = vec!["tags".to_string()];
+
+    let mut dir_node = crate::DirNode::build(opts.source.join("unit_tests/blog"), &opts)?;
+    let global = MetaFile::build(opts.source.join("default.meta"), &opts)?;
+    dir_node.map(&global)?;
+    dir_node.build_taxonomies()?;
+
+    assert_eq!(
+        fs::read_to_string(opts.build.join("tags/rust.html"))?,
+        "<a href=\"/unit_tests/blog/first.html\">First Post</a>\n<a href=\"/unit_tests/blog/second.html\">Second Post</a>\n\n"
+    );
+
+    assert_eq!(
+        fs::read_to_string(opts.build.join("tags/web-dev.html"))?,
+        "<a href=\"/unit_tests/blog/first.html\">First Post</a>\n\n"
+    );
+
+    assert_eq!(
+        fs::read_to_string(opts.build.join("tags/index.html"))?,
+        "<a href=\"/tags/rust.html\">rust</a>\n<a href=\"/tags/web-dev.html\">web dev</a>\n\n"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_taxonomy_collision() -> Result<()> {
+    let mut opts = test_opts();
+    opts.source = PathBuf::from("files/collisions/taxonomy").canonicalize()?;
+    opts.taxonomies = vec!["tags".to_string()];
+
+    let dir_node = mapped_dir(opts.source.clone(), &opts)?;
+
+    // source/tags/rust.meta builds to the same place as the rust tag
+    let error = dir_node.check_collisions().unwrap_err();
+    match error.downcast_ref::<MetaError>() {
+        Some(MetaError::Collision { path, second, .. }) => {
+            assert!(path.ends_with("build/tags/rust.html"));
+            assert_eq!(second, "the tags term 'rust'");
+        }
+        _ => panic!("expected a collision, got {error}"),
+    }
+
+    // c+ and c# are different terms with the same filename
+    opts.source = PathBuf::from("files/collisions/terms").canonicalize()?;
+    let dir_node = mapped_dir(opts.source.clone(), &opts)?;
+
+    let error = dir_node.check_collisions().unwrap_err();
+    assert!(error
+        .to_string()
+        .starts_with("the tags term 'C#' and the tags term 'C+' both build to"));
+
+    Ok(())
+}
+
+#[test]
+fn test_taxonomy_slugs() -> Result<()> {
+    let mut opts = test_opts();
+    opts.source = PathBuf::from("files/taxonomy").canonicalize()?;
+    opts.taxonomies = vec!["tags".to_string()];
+
+    let dir_node = mapped_dir(opts.source.clone(), &opts)?;
+    dir_node.check_collisions()?;
+
+    let urls = dir_node
+        .taxonomy_pages()?
+        .iter()
+        .map(|page| page.url())
+        .collect::<Result<Vec<String>>>()?;
+    assert_eq!(
+        urls,
+        [
+            "/tags/2024.html",
+            "/tags/c-.html",
+            "/tags/c--.html",
+            "/tags/web-dev.html",
+            "/tags/index.html"
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_minify() -> Result<()> {
+    let opts = test_opts();
+
+    let mut unminified = opts.clone();
+    unminified.minify = false;
+
+    let dest = opts.build.join("unit_tests/minify/page.html");
+
+    // parallel builds minify the same as serial ones
+    let mut dir_node = mapped_dir(opts.source.join("unit_tests/minify"), &opts)?;
+    dir_node.par_file()?;
+    assert_eq!(fs::read_to_string(&dest)?, "<a href=/page.html>page</a>");
+
+    let mut dir_node = mapped_dir(opts.source.join("unit_tests/minify"), &unminified)?;
+    dir_node.par_file()?;
+    assert_eq!(
+        fs::read_to_string(&dest)?,
+        "<a href=\"/page.html\">page</a>\n\n"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_pagination() -> Result<()> {
+    let mut opts = test_opts();
+    opts.minify = false;
+
+    let path = opts.source.join("unit_tests/expand/paginate.meta");
+    let mut file = MetaFile::build(path, &opts)?;
+    file.render()?;
+
+    assert_eq!(
+        fs::read_to_string(opts.build.join("unit_tests/expand/paginate.html"))?,
+        "<a href=\"/unit_tests/blog/first.html\">First Post</a>\n\n1/2 [] [/unit_tests/expand/paginate/page/2/index.html]\n\n"
+    );
+
+    assert_eq!(
+        fs::read_to_string(opts.build.join("unit_tests/expand/paginate/page/2/index.html"))?,
+        "<a href=\"/unit_tests/blog/second.html\">Second Post</a>\n\n2/2 [/unit_tests/expand/paginate.html] []\n\n"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_pagination_dirs() -> Result<()> {
+    let opts = test_opts();
+
+    let dir_node = mapped_dir(opts.source.join("unit_tests/paginate"), &opts)?;
+
+    // index and archive both paginate in the same directory
+    dir_node.check_collisions()?;
+
+    let dests = dir_node
+        .files
+        .iter()
+        .map(|file| file.paginate()?[1].dest())
+        .collect::<Result<Vec<PathBuf>>>()?;
+    assert_eq!(
+        dests,
+        [
+            opts.build
+                .join("unit_tests/paginate/archive/page/2/index.html"),
+            opts.build.join("unit_tests/paginate/page/2/index.html")
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_pagination_permalink() -> Result<()> {
+    let opts = test_opts();
+
+    let path = opts.source.join("unit_tests/paginate_permalink/news.meta");
+    let file = MetaFile::build(path, &opts)?;
+    let pages = file.paginate()?;
+
+    // the permalink is expanded with the file's own variables on every page
+    assert_eq!(
+        pages[1].dest()?,
+        opts.build.join("unit_tests/news/page/2/index.html")
+    );
+    assert_eq!(
+        pages[0].get_variable("PAGINATION.next")?,
+        "/unit_tests/news/page/2/"
+    );
+    assert_eq!(
+        pages[1].get_variable("PAGINATION.prev")?,
+        "/unit_tests/news/"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_feed() -> Result<()> {
+    let mut opts = test_opts();
+    opts.source = PathBuf::from("files/feed").canonicalize()?;
+    opts.base_url = "https://example.com".to_string();
+
+    let dir_node = mapped_dir(opts.source.join("blog"), &opts)?;
+    dir_node.build_feed()?;
+
+    assert_eq!(
+        fs::read_to_string(opts.build.join("blog/feed.xml"))?,
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>
+<rss version=\"2.0\">
+<channel>
+<title>Blog &amp; News</title>
+<link>https://example.com/blog/</link>
+<description></description>
+<item>
+<title>Second Post</title>
+<link>https://example.com/blog/second.html</link>
+<guid>https://example.com/blog/second.html</guid>
+<pubDate>Wed, 1 Feb 2023 00:00:00 +0000</pubDate>
+</item>
+<item>
+<title>First Post</title>
+<link>https://example.com/blog/first.html</link>
+<guid>https://example.com/blog/first.html</guid>
+<pubDate>Sun, 1 Jan 2023 00:00:00 +0000</pubDate>
+<description>the &lt;first&gt; one</description>
+</item>
+</channel>
+</rss>
+"
+    );
+
+    let atom = fs::read_to_string(opts.build.join("blog/atom.xml"))?;
+    assert!(atom.contains("<updated>2023-02-01T00:00:00+00:00</updated>"));
+    assert!(atom.contains("<summary>the &lt;first&gt; one</summary>"));
+
+    // the feed author comes first, then any authors of single entries
+    assert_eq!(atom.matches("<name>").count(), 2);
+    assert!(atom.contains("</updated>\n<author>\n<name>Blog Team</name>\n</author>\n<entry>"));
+    assert!(
+        atom.contains("<updated>2023-02-01T00:00:00+00:00</updated>\n<author>\n<name>Sam</name>")
+    );
+
+    // feeds can't be built with relative links
+    let mut relative = opts.clone();
+    relative.base_url = String::new();
+
+    let dir_node = mapped_dir(relative.source.join("blog"), &relative)?;
+
+    assert_eq!(dir_node.feed_dirs()?, [relative.source.join("blog")]);
+    let error = dir_node.build_feed().unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<MetaError>(),
+        Some(MetaError::BaseUrl { .. })
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_sitemap() -> Result<()> {
+    let mut opts = test_opts();
+    opts.source = PathBuf::from("files/sitemap").canonicalize()?;
+    opts.base_url = "https://example.com".to_string();
+    opts.sitemap = true;
+    opts.force = true;
+    opts.taxonomies = vec!["topics".to_string()];
+
+    let mut dir_node = mapped_dir(opts.source.clone(), &opts)?;
+    let mut built = dir_node.build_taxonomies()?;
+    built.append(&mut dir_node.build_dir()?);
+    crate::build_sitemap(&opts, &built)?;
+
+    // broken.meta fails to build, and hidden.meta opts out
+    let sitemap = fs::read_to_string(opts.build.join("sitemap.xml"))?;
+    let locs: Vec<&str> = sitemap
+        .lines()
+        .filter_map(|line| line.strip_prefix("<loc>")?.strip_suffix("</loc>"))
+        .collect();
+    assert_eq!(
+        locs,
+        [
+            "https://example.com/good.html",
+            "https://example.com/topics/index.html",
+            "https://example.com/topics/maps.html"
+        ]
+    );
+
+    // generated pages don't have a source file to date them by
+    assert_eq!(sitemap.matches("<lastmod>").count(), 1);
+
+    let mut relative = opts.clone();
+    relative.base_url = String::new();
+    let error = crate::build_sitemap(&relative, &built).unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<MetaError>(),
+        Some(MetaError::BaseUrl { .. })
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_nav() -> Result<()> {
+    let opts = test_opts();
+
+    let path = opts.source.join("unit_tests/nav/sub/page.meta");
+    let file = MetaFile::build(path, &opts)?;
+    let nav = file.build_nav(&opts.source.join("unit_tests/nav"))?;
+
+    assert_eq!(
+        file.mark_active(&nav)?,
+        "<ul>
+<li><a href=\"/unit_tests/nav/b.html\">B</a></li>
+<li><a href=\"/unit_tests/nav/a.html\">A</a></li>
+<li><a href=\"/unit_tests/nav/index.html\">Nav Home</a></li>
+<li><a href=\"/unit_tests/nav/sub/index.html\">Sub</a>
+<ul>
+<li class=\"active\"><a href=\"/unit_tests/nav/sub/page.html\" aria-current=\"page\">Sub &amp; Page</a></li>
+</ul>
+</li>
+</ul>
+"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_nav_lazy() -> Result<()> {
+    let opts = test_opts();
+
+    // nothing in minify/ uses NAV, so the tree never gets built
+    let mut dir_node = mapped_dir(opts.source.join("unit_tests/minify"), &opts)?;
+    dir_node.build_files()?;
+    assert!(dir_node.global.nav.get().is_none());
+
+    // every page in nav/ shares the tree built by the first one
+    let mut dir_node = mapped_dir(opts.source.join("unit_tests/nav"), &opts)?;
+    dir_node.build_files()?;
+    assert!(dir_node.global.nav.get().is_some());
+    assert!(dir_node
+        .files
+        .iter()
+        .all(|file| std::sync::Arc::ptr_eq(&file.nav, &dir_node.global.nav)));
+
+    Ok(())
+}
+
+#[test]
+fn test_siblings() -> Result<()> {
+    let opts = test_opts();
+
+    let mut dir_node = mapped_dir(opts.source.join("unit_tests/nav"), &opts)?;
+    dir_node.link_siblings()?;
+
+    let file = |name: &str| {
+        dir_node
+            .files
+            .iter()
+            .find(|file| file.path.ends_with(name))
+            .unwrap()
+    };
+
+    // b has a lower weight, so it comes before a
+    assert_eq!(file("b.meta").get_variable("PREV.url")?, "");
+    assert_eq!(file("b.meta").get_variable("NEXT.title")?, "A Long Title");
+    assert_eq!(file("a.meta").get_variable("PREV.title")?, "B");
+    assert_eq!(
+        file("a.meta").get_variable("NEXT.url")?,
+        "/unit_tests/nav/hidden.html"
+    );
+    assert_eq!(file("index.meta").get_variable("NEXT.url")?, "");
+
+    Ok(())
+}
+
+#[test]
+fn test_ordering() -> Result<()> {
+    let opts = test_opts();
+
+    let dir_node = mapped_dir(opts.source.join("unit_tests/nav"), &opts)?;
+
+    let files: Vec<String> = dir_node.files.iter().map(|f| f.name().unwrap()).collect();
+    assert_eq!(
+        files,
+        [
+            "unit_tests.nav.a",
+            "unit_tests.nav.b",
+            "unit_tests.nav.hidden",
+            "unit_tests.nav.index"
+        ]
+    );
+
+    // collections default to weight, then filename
+    let file = MetaFile::new(&opts);
+    assert_eq!(
+        file.get_collection("PAGES.unit_tests.nav.title")?,
+        Some(vec![
+            "B".to_string(),
+            "A Long Title".to_string(),
+            String::new(),
+            "Nav Home".to_string()
+        ])
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_drafts() -> Result<()> {
+    let mut opts = test_opts();
+
+    for name in ["draft", "future"] {
+        let path = opts.source.join(format!("unit_tests/drafts/{name}.meta"));
+        let mut file = MetaFile::build(path, &opts)?;
+        assert!(matches!(
+            file.construct().map_err(|e| *e),
+            Err(MetaError::Ignored)
+        ));
+    }
+
+    let file = MetaFile::new(&opts);
+    assert_eq!(
+        file.get_collection("PAGES.unit_tests.drafts.title")?,
+        Some(vec!["Past".to_string()])
+    );
+
+    opts.drafts = true;
+    opts.future = true;
+
+    let path = opts.source.join("unit_tests/drafts/future.meta");
+    assert_eq!(MetaFile::build(path, &opts)?.construct()?, "future\n\n");
+
+    let file = MetaFile::new(&opts);
+    assert_eq!(
+        file.get_collection("PAGES.unit_tests.drafts.title")?,
+        Some(vec![
+            "Draft".to_string(),
+            "Future".to_string(),
+            "Past".to_string()
+        ])
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_permalink() -> Result<()> {
+    let opts = test_opts();
+
+    let path = opts.source.join("unit_tests/permalink/post.meta");
+    let file = MetaFile::build(path, &opts)?;
+
+    assert_eq!(
+        file.dest()?,
+        opts.build.join("archive/2023/moved/index.html")
+    );
+    assert_eq!(file.url()?, "/archive/2023/moved/");
+
+    Ok(())
+}
+
+#[test]
+fn test_permalink_collision() -> Result<()> {
+    let mut opts = test_opts();
+    opts.source = PathBuf::from("files/collisions/permalink").canonicalize()?;
+
+    let dir_node = mapped_dir(opts.source.clone(), &opts)?;
+
+    let error = dir_node.check_collisions().unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<MetaError>(),
+        Some(MetaError::Collision { .. })
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_generated_collision() -> Result<()> {
+    let mut opts = test_opts();
+    opts.source = PathBuf::from("files/collisions/generated").canonicalize()?;
+    opts.base_url = "https://example.com".to_string();
+
+    let dir_node = mapped_dir(opts.source.clone(), &opts)?;
+
+    // feed.meta has a permalink of /feed.xml
+    let error = dir_node.check_collisions().unwrap_err();
+    match error.downcast_ref::<MetaError>() {
+        Some(MetaError::Collision { path, second, .. }) => {
+            assert!(path.ends_with("build/feed.xml"));
+            assert!(second.starts_with("the feed for"));
+        }
+        _ => panic!("expected a collision, got {error}"),
+    }
+
+    // sitemap.meta has a permalink of /sitemap.xml
+    opts.source = opts.source.join("sitemap");
+    opts.sitemap = true;
+    let dir_node = mapped_dir(opts.source.clone(), &opts)?;
+
+    let error = dir_node.check_collisions().unwrap_err();
+    match error.downcast_ref::<MetaError>() {
+        Some(MetaError::Collision { path, second, .. }) => {
+            assert!(path.ends_with("build/sitemap.xml"));
+            assert_eq!(second, "the sitemap");
+        }
+        _ => panic!("expected a collision, got {error}"),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_pretty_urls() -> Result<()> {
+    let opts = test_opts();
+
+    let mut pretty = opts.clone();
+    pretty.pretty_urls = true;
+
+    let source = opts.source.clone();
+    let page = |path: &str, opts| MetaFile::build_page(source.join(path), opts);
+
+    let file = page("unit_tests/pretty/page.meta", &opts)?;
+    assert_eq!(
+        file.dest()?,
+        opts.build.join("unit_tests/pretty/page/index.html")
+    );
+    assert_eq!(file.url()?, "/unit_tests/pretty/page/");
+
+    let file = page("unit_tests/pretty/index.meta", &opts)?;
+    assert_eq!(file.url()?, "/unit_tests/pretty/");
+
+    let file = page("unit_tests/pretty/ugly.meta", &opts)?;
+    assert_eq!(file.url()?, "/unit_tests/pretty/ugly.html");
+
+    let file = page("unit_tests/find_dest.meta", &opts)?;
+    assert_eq!(file.url()?, "/unit_tests/find_dest.html");
+
+    let file = page("unit_tests/find_dest.meta", &pretty)?;
+    assert_eq!(file.url()?, "/unit_tests/find_dest/");
+
+    Ok(())
+}
+
+#[test]
+fn test_aliases() -> Result<()> {
+    let mut opts = test_opts();
+    opts.minify = false;
+    opts.redirects = true;
+
+    let path = opts.source.join("unit_tests/aliases/moved.meta");
+    let mut file = MetaFile::build(path, &opts)?;
+    file.render()?;
+
+    for alias in ["old/moved.html", "older/index.html"] {
+        let redirect = fs::read_to_string(opts.build.join(alias))?;
+        assert!(redirect.contains(
+            "<meta http-equiv=\"refresh\" content=\"0; url=/unit_tests/aliases/moved.html\">"
+        ));
+    }
+
+    let dir_node = mapped_dir(opts.source.join("unit_tests/aliases"), &opts)?;
+    dir_node.build_redirects()?;
+
+    assert_eq!(
+        fs::read_to_string(opts.build.join("_redirects"))?,
+        "/old/moved.html /unit_tests/aliases/moved.html 301\n/older/ /unit_tests/aliases/moved.html 301\n"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_formats() -> Result<()> {
+    let mut opts = test_opts();
+    opts.minify = false;
+
+    let path = opts.source.join("unit_tests/formats/resume.meta");
+    let mut file = MetaFile::build(path, &opts)?;
+    assert_eq!(file.url()?, "/unit_tests/formats/resume.html");
+
+    file.render()?;
+
+    assert_eq!(
+        fs::read_to_string(opts.build.join("unit_tests/formats/resume.html"))?,
+        "<html>resume\n</html>\n"
+    );
+    assert_eq!(
+        fs::read_to_string(opts.build.join("unit_tests/formats/resume.txt"))?,
+        "TEXT resume\n\n"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_binary_source() -> Result<()> {
+    let mut opts = test_opts();
+    opts.no_pandoc = true;
+
+    // binary output is built through the base pattern without converting
+    let path = opts.source.join("unit_tests/binary/doc.meta");
+    let mut file = MetaFile::build(path, &opts)?;
+    assert_eq!(file.construct()?, "# Doc\n\nsome *markdown*\n\n");
+
+    assert!(matches!(
+        file.construct_bytes().map_err(|e| *e),
+        Err(MetaError::Binary { .. })
+    ));
+
+    // the check happens before anything gets expanded
+    opts.pattern = PathBuf::from("files/missing");
+    let path = opts.source.join("unit_tests/binary/doc.meta");
+    let mut file = MetaFile::build(path, &opts)?;
+    assert!(file.construct().is_err());
+    assert!(matches!(
+        file.construct_bytes().map_err(|e| *e),
+        Err(MetaError::Binary { .. })
+    ));
+
+    // skipped files stay skipped
+    file.header.ignore = true;
+    assert!(matches!(
+        file.construct_bytes().map_err(|e| *e),
+        Err(MetaError::Ignored)
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_binary_output() -> Result<()> {
+    let opts = test_opts();
+
+    let path = opts.source.join("unit_tests/binary/doc.meta");
+    let mut file = MetaFile::build(path, &opts)?;
+    file.render()?;
+
+    // docx files are zip archives
+    let bytes = fs::read(opts.build.join("unit_tests/binary/doc.docx"))?;
+    assert!(bytes.starts_with(b"PK"));
+
+    Ok(())
+}